@@ -6,7 +6,93 @@ pub enum CustomError {
     #[error("Arithmetic error!")]
     ArithmeticError,
     #[error("amount entered in redemption is not valid!")]
-    InvalidRedemptionAmount
+    InvalidRedemptionAmount,
+    #[error("investor has too many open redemption requests!")]
+    TooManyOpenRedemptions,
+    #[error("redemption can no longer be cancelled!")]
+    CancellationWindowClosed,
+    #[error("share value is older than the fund allows!")]
+    StaleShareValue,
+    #[error("share value must be greater than zero!")]
+    InvalidShareValue,
+    #[error("share value change exceeds the fund's allowed band!")]
+    ShareValueChangeTooLarge,
+    #[error("fund has no oracle configured!")]
+    OracleNotConfigured,
+    #[error("share value is sourced from the fund's oracle!")]
+    ShareValueSourcedFromOracle,
+    #[error("oracle price is older than the fund allows!")]
+    StaleOraclePrice,
+    #[error("oracle price confidence interval is too wide!")]
+    OracleConfidenceTooWide,
+    #[error("fund does not use share value attestation!")]
+    NavAttestationDisabled,
+    #[error("share value must be proposed and attested!")]
+    ShareValueRequiresAttestation,
+    #[error("invalid share value attester configuration!")]
+    InvalidNavAttesters,
+    #[error("signer is not a share value attester of this fund!")]
+    NotANavAttester,
+    #[error("there is no pending share value proposal!")]
+    NoPendingShareValue,
+    #[error("share value proposal is past its attestation window!")]
+    NavProposalExpired,
+    #[error("attester has already approved this proposal!")]
+    DuplicateNavAttestation,
+    #[error("fund only accepts forward priced subscriptions!")]
+    ForwardPricingRequired,
+    #[error("invalid subscription amount!")]
+    InvalidSubscriptionAmount,
+    #[error("request must wait for the next share value!")]
+    AwaitingNextShareValue,
+    #[error("dealing share value is not available in the nav history!")]
+    DealingShareValueUnavailable,
+    #[error("invalid dealing schedule!")]
+    InvalidDealingSchedule,
+    #[error("request is outside the fund's dealing window!")]
+    OutsideDealingWindow,
+    #[error("request cannot be processed before its dealing date!")]
+    BeforeDealingDate,
+    #[error("redemption notice period has not elapsed!")]
+    NoticePeriodNotElapsed,
+    #[error("redemption settlement deadline has not passed!")]
+    SettlementDeadlineNotReached,
+    #[error("redemption is already flagged as overdue!")]
+    RedemptionAlreadyEscalated,
+    #[error("invalid redemption gate!")]
+    InvalidRedemptionGate,
+    #[error("redemption gate is closed for this period!")]
+    RedemptionGateClosed,
+    #[error("shares are still inside the lock-up period!")]
+    SharesLockedUp,
+    #[error("amount is below the fund's minimum initial investment!")]
+    BelowMinimumInitialInvestment,
+    #[error("amount is below the fund's minimum top-up!")]
+    BelowMinimumTopUp,
+    #[error("shares are below the fund's minimum redemption!")]
+    BelowMinimumRedemption,
+    #[error("holding would exceed the fund's maximum per investor!")]
+    AboveMaximumHolding,
+    #[error("amount is too small to issue any shares!")]
+    ZeroSharesIssued,
+    #[error("investor is not approved for this fund!")]
+    InvestorNotApproved,
+    #[error("investor kyc has expired!")]
+    KycExpired,
+    #[error("investor has been revoked!")]
+    InvestorRevoked,
+    #[error("share transfers are blocked!")]
+    ShareTransfersBlocked,
+    #[error("fund mint has no metadata!")]
+    MintHasNoMetadata,
+    #[error("invalid share decimals!")]
+    InvalidShareDecimals,
+    #[error("fee basis points exceed 100%!")]
+    InvalidFeeBasisPoints,
+    #[error("share value attesters must approve this change!")]
+    NavAttesterApprovalRequired,
+    #[error("the risk officer must approve this change!")]
+    RiskOfficerApprovalRequired
 }
 
 impl From<CustomError> for ProgramError {
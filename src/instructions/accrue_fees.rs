@@ -0,0 +1,46 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::fund_account::FundAccount;
+
+pub fn accrue_fees(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fund_name: String
+) -> ProgramResult {
+    let [
+        payer,
+        fund_account,
+        mint_account,
+        manager_fee_recipient,
+        manager_fee_ata,
+        platform_fee_recipient,
+        platform_fee_ata,
+        system_program,
+        token_program,
+        associated_token_account_program
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    FundAccount::accrue_fees(
+        program_id, 
+        payer, 
+        fund_account, 
+        mint_account, 
+        manager_fee_recipient, 
+        manager_fee_ata, 
+        platform_fee_recipient,
+        platform_fee_ata,
+        system_program, 
+        token_program, 
+        associated_token_account_program, 
+        fund_name
+    )?;
+    
+    Ok(())
+}
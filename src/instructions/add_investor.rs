@@ -0,0 +1,43 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::investor_record::InvestorRecord;
+
+pub fn add_investor(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    expiry_timestamp: i64,
+    jurisdiction: [u8; 2],
+    fund_name: String
+) -> ProgramResult {
+
+    let [
+        punto_xero_master,
+        compliance_officer,
+        fund_account,
+        investor_record_account,
+        investor,
+        system_program
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    InvestorRecord::add_investor(
+        program_id, 
+        punto_xero_master, 
+        compliance_officer, 
+        fund_account, 
+        investor_record_account, 
+        investor, 
+        system_program, 
+        expiry_timestamp, 
+        jurisdiction, 
+        fund_name
+    )?;
+
+    Ok(())
+}
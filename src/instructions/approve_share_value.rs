@@ -0,0 +1,42 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::pending_nav::PendingNav;
+
+pub fn approve_share_value(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fund_name: String
+) -> ProgramResult {
+    let [
+        attester,
+        fund_account,
+        mint_account,
+        pending_nav_account,
+        nav_history_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let nav_history_account = match nav_history_accounts {
+        [] => None,
+        [nav_history_account] => Some(nav_history_account),
+        _ => return Err(ProgramError::NotEnoughAccountKeys)
+    };
+
+    PendingNav::approve_share_value(
+        program_id, 
+        attester, 
+        fund_account, 
+        mint_account,
+        pending_nav_account,
+        nav_history_account,
+        fund_name
+    )?;
+    
+    Ok(())
+}
@@ -5,7 +5,7 @@ use solana_program::{
     pubkey::Pubkey
 };
 
-use crate::state::fund_account::FundAccount;
+use crate::state::{fund_account::FundAccount, investor_record::InvestorRecord};
 
 pub fn buy_fund_shares(
     program_id: &Pubkey,
@@ -22,11 +22,29 @@ pub fn buy_fund_shares(
         buyer_ata,
         system_program,
         token_program,
-        associated_token_account_program
+        associated_token_account_program,
+        payment_accounts @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    let (investor_record_account, payment_accounts) = InvestorRecord::split_trailing(
+        program_id, 
+        fund_account.key, 
+        buyer.key, 
+        payment_accounts
+    );
+
+    let (payment_accounts, investor_lots_account) = match payment_accounts {
+        [] => (None, None),
+        [investor_lots_account] => (None, Some(investor_lots_account)),
+        [buyer_payment_account, cash_vault, settlement_token_program] 
+            => (Some((buyer_payment_account, cash_vault, settlement_token_program)), None),
+        [buyer_payment_account, cash_vault, settlement_token_program, investor_lots_account] 
+            => (Some((buyer_payment_account, cash_vault, settlement_token_program)), Some(investor_lots_account)),
+        _ => return Err(ProgramError::NotEnoughAccountKeys)
+    };
+
     FundAccount::buy_fund_shares(
         program_id, 
         punto_xero_master, 
@@ -37,6 +55,9 @@ pub fn buy_fund_shares(
         system_program, 
         token_program,
         associated_token_account_program,
+        payment_accounts,
+        investor_lots_account,
+        investor_record_account,
         amount_in_fiat, 
         &fund_name
     )?;
@@ -0,0 +1,45 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey,
+};
+
+use crate::state::share_redemption::ShareRedemption;
+
+pub fn cancel_redemption(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fund_name: String,
+) -> ProgramResult {
+
+    let [
+        punto_xero_master,
+        fund_account,
+        mint_account,
+        fund_vault,
+        share_redemption_account,
+        investor_redemptions_account,
+        investor,
+        investor_ata,
+        token_program,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    ShareRedemption::cancel_share_redemption(
+        program_id, 
+        punto_xero_master, 
+        fund_account, 
+        mint_account, 
+        fund_vault,
+        share_redemption_account, 
+        investor_redemptions_account,
+        investor, 
+        investor_ata, 
+        token_program, 
+        fund_name
+    )?;
+
+    Ok(())
+}
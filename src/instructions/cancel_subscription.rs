@@ -0,0 +1,46 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::subscription_request::SubscriptionRequest;
+
+pub fn cancel_subscription(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fund_name: String
+) -> ProgramResult {
+
+    let [
+        punto_xero_master,
+        fund_account,
+        subscription_request_account,
+        investor,
+        token_program,
+        payment_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let payment_accounts = match payment_accounts {
+        [] => None,
+        [investor_payment_account, cash_vault, settlement_token_program] 
+            => Some((investor_payment_account, cash_vault, settlement_token_program)),
+        _ => return Err(ProgramError::NotEnoughAccountKeys)
+    };
+
+    SubscriptionRequest::cancel_subscription_request(
+        program_id, 
+        punto_xero_master, 
+        fund_account, 
+        subscription_request_account, 
+        investor, 
+        token_program,
+        payment_accounts,
+        fund_name
+    )?;
+
+    Ok(())
+}
@@ -11,7 +11,10 @@ pub fn init_fund(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     fund_name: String,
-    share_value: u64
+    share_value: u64,
+    share_decimals: u8,
+    symbol: String,
+    uri: String
 ) -> ProgramResult {
     let [
         punto_xero_master, 
@@ -23,10 +26,23 @@ pub fn init_fund(
         token_program,
         associated_token_account_program,
         rent_sysvar,
+        trailing_accounts @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    let (settlement_accounts, extra_account_metas_account) = match trailing_accounts {
+        [] => (None, None),
+        [extra_account_metas] => (None, Some(extra_account_metas)),
+        [settlement_mint, cash_vault, settlement_token_program] => {
+            (Some((settlement_mint, cash_vault, settlement_token_program)), None)
+        },
+        [settlement_mint, cash_vault, settlement_token_program, extra_account_metas] => {
+            (Some((settlement_mint, cash_vault, settlement_token_program)), Some(extra_account_metas))
+        },
+        _ => return Err(ProgramError::NotEnoughAccountKeys)
+    };
+
     FundAccount::initialize_fund(
         program_id, 
         punto_xero_master, 
@@ -38,7 +54,12 @@ pub fn init_fund(
         token_program,
         associated_token_account_program,
         rent_sysvar,
+        settlement_accounts,
+        extra_account_metas_account,
         share_value, 
+        share_decimals,
+        symbol,
+        uri,
         &fund_name
     )?;
 
@@ -0,0 +1,33 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::share_redemption::ShareRedemption;
+
+pub fn escalate_redemption(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fund_name: String,
+) -> ProgramResult {
+
+    let [
+        fund_account,
+        share_redemption_account,
+        investor,
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    ShareRedemption::escalate_share_redemption(
+        program_id, 
+        fund_account, 
+        share_redemption_account, 
+        investor, 
+        fund_name
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::transfer_hook::check_share_transfer;
+
+pub fn execute_transfer_hook(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo]
+) -> ProgramResult {
+
+    let [
+        source,
+        mint_account,
+        destination,
+        _authority,
+        _extra_account_metas,
+        fund_account,
+        source_record,
+        destination_record
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    check_share_transfer(
+        program_id, 
+        source, 
+        mint_account, 
+        destination, 
+        fund_account, 
+        source_record, 
+        destination_record
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::nav_history::NavHistory;
+
+pub fn init_nav_history(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fund_name: String
+) -> ProgramResult {
+    let [
+        punto_xero_master,
+        manager_master,
+        fund_account,
+        nav_history_account,
+        system_program
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    NavHistory::initialize_nav_history(
+        program_id, 
+        punto_xero_master, 
+        manager_master, 
+        fund_account, 
+        nav_history_account, 
+        system_program, 
+        fund_name
+    )?;
+    
+    Ok(())
+}
@@ -3,21 +3,67 @@ pub mod update_share_value;
 pub mod buy_fund_shares;
 pub mod redeem_shares;
 pub mod process_shares_redemption;
+pub mod update_fund_config;
+pub mod cancel_redemption;
+pub mod accrue_fees;
+pub mod init_nav_history;
+pub mod override_share_value;
+pub mod refresh_share_value_from_oracle;
+pub mod propose_share_value;
+pub mod approve_share_value;
+pub mod request_subscription;
+pub mod process_subscription;
+pub mod escalate_redemption;
+pub mod add_investor;
+pub mod suspend_investor;
+pub mod revoke_investor;
+pub mod execute_transfer_hook;
+pub mod freeze_investor;
+pub mod thaw_investor;
+pub mod update_share_metadata;
+pub mod cancel_subscription;
+pub mod reject_subscription;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::program_error::ProgramError;
+use spl_transfer_hook_interface::instruction::TransferHookInstruction;
+
+use crate::state::fund_account::FundConfig;
 
 pub enum Instructions {
-    InitFundAccount { share_value: u64, fund_name: String },
+    InitFundAccount { share_value: u64, share_decimals: u8, symbol: String, uri: String, fund_name: String },
     UpdateShareValue { new_share_value: u64, fund_name: String },
     BuyFundShares { amount_in_fiat: u64, fund_name: String },
     RedeemShares { shares_to_redeem: u64, fund_name: String },
-    ProcessSharesRedemption { amount_payed: u64, fund_name: String }
+    ProcessSharesRedemption { shares_to_process: u64, amount_payed: u64, fund_name: String },
+    UpdateFundConfig { config: Box<FundConfig>, fund_name: String },
+    CancelRedemption { fund_name: String },
+    AccrueFees { fund_name: String },
+    InitNavHistory { fund_name: String },
+    OverrideShareValue { new_share_value: u64, fund_name: String },
+    RefreshShareValueFromOracle { fund_name: String },
+    ProposeShareValue { proposed_share_value: u64, fund_name: String },
+    ApproveShareValue { fund_name: String },
+    RequestSubscription { amount_in_fiat: u64, fund_name: String },
+    ProcessSubscription { fund_name: String },
+    EscalateRedemption { fund_name: String },
+    AddInvestor { expiry_timestamp: i64, jurisdiction: [u8; 2], fund_name: String },
+    SuspendInvestor { fund_name: String },
+    RevokeInvestor { fund_name: String },
+    ExecuteTransferHook,
+    FreezeInvestor { fund_name: String },
+    ThawInvestor { fund_name: String },
+    UpdateShareMetadata { symbol: String, uri: String, fund_name: String },
+    CancelSubscription { fund_name: String },
+    RejectSubscription { fund_name: String }
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Debug)]
 pub struct FundInitPayload {
     pub share_value: u64,
+    pub share_decimals: u8,
+    pub symbol: String,
+    pub uri: String,
     pub fund_name: String
 }
 
@@ -41,12 +87,89 @@ pub struct RedeemSharesPayload {
 
 #[derive(BorshDeserialize, BorshSerialize, Debug)]
 pub struct ProcessSharesRedemptionPayload {
+    pub shares_to_process: u64,
     pub amount_payed: u64,
     pub fund_name: String
 }
 
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct UpdateFundConfigPayload {
+    pub config: FundConfig,
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct CancelRedemptionPayload {
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct AccrueFeesPayload {
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct InitNavHistoryPayload {
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RefreshShareValueFromOraclePayload {
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct ApproveShareValuePayload {
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct ProcessSubscriptionPayload {
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct EscalateRedemptionPayload {
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct AddInvestorPayload {
+    pub expiry_timestamp: i64,
+    pub jurisdiction: [u8; 2],
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct InvestorStatusPayload {
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct UpdateShareMetadataPayload {
+    pub symbol: String,
+    pub uri: String,
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct CancelSubscriptionPayload {
+    pub fund_name: String
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Debug)]
+pub struct RejectSubscriptionPayload {
+    pub fund_name: String
+}
+
 impl Instructions {
     pub fn unpack(instruction_data: &[u8]) -> Result<Self, ProgramError> {
+        // token-2022 calls into the fund mint's transfer hook with its own
+        // 8 byte discriminator
+        if let Ok(TransferHookInstruction::Execute { .. }) = TransferHookInstruction::unpack(instruction_data) {
+            return Ok(Self::ExecuteTransferHook);
+        }
+
         let (discriminator, data) = instruction_data
             .split_first()
             .ok_or(ProgramError::InvalidInstructionData)?;
@@ -58,6 +181,9 @@ impl Instructions {
 
                 Ok(Self::InitFundAccount {
                     share_value: payload.share_value,
+                    share_decimals: payload.share_decimals,
+                    symbol: payload.symbol,
+                    uri: payload.uri,
                     fund_name: payload.fund_name
                 })
             },
@@ -92,7 +218,145 @@ impl Instructions {
                 let payload = ProcessSharesRedemptionPayload::try_from_slice(data)
                     .map_err(|_| ProgramError::InvalidInstructionData)?;
 
-                Ok(Self::ProcessSharesRedemption { amount_payed: payload.amount_payed, fund_name: payload.fund_name })
+                Ok(Self::ProcessSharesRedemption { 
+                    shares_to_process: payload.shares_to_process, 
+                    amount_payed: payload.amount_payed, 
+                    fund_name: payload.fund_name 
+                })
+            },
+            5 => {
+                let payload = UpdateFundConfigPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::UpdateFundConfig { 
+                    config: Box::new(payload.config), 
+                    fund_name: payload.fund_name 
+                })
+            },
+            6 => {
+                let payload = CancelRedemptionPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::CancelRedemption { fund_name: payload.fund_name })
+            },
+            7 => {
+                let payload = AccrueFeesPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::AccrueFees { fund_name: payload.fund_name })
+            },
+            8 => {
+                let payload = InitNavHistoryPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::InitNavHistory { fund_name: payload.fund_name })
+            },
+            9 => {
+                let payload = UpdateShareValuePayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::OverrideShareValue {
+                    new_share_value: payload.new_share_value,
+                    fund_name: payload.fund_name
+                })
+            },
+            10 => {
+                let payload = RefreshShareValueFromOraclePayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::RefreshShareValueFromOracle { fund_name: payload.fund_name })
+            },
+            11 => {
+                let payload = UpdateShareValuePayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::ProposeShareValue {
+                    proposed_share_value: payload.new_share_value,
+                    fund_name: payload.fund_name
+                })
+            },
+            12 => {
+                let payload = ApproveShareValuePayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::ApproveShareValue { fund_name: payload.fund_name })
+            },
+            13 => {
+                let payload = BuyFundSharesPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::RequestSubscription {
+                    amount_in_fiat: payload.amount_in_fiat,
+                    fund_name: payload.fund_name
+                })
+            },
+            14 => {
+                let payload = ProcessSubscriptionPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::ProcessSubscription { fund_name: payload.fund_name })
+            },
+            15 => {
+                let payload = EscalateRedemptionPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::EscalateRedemption { fund_name: payload.fund_name })
+            },
+            16 => {
+                let payload = AddInvestorPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::AddInvestor {
+                    expiry_timestamp: payload.expiry_timestamp,
+                    jurisdiction: payload.jurisdiction,
+                    fund_name: payload.fund_name
+                })
+            },
+            17 => {
+                let payload = InvestorStatusPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::SuspendInvestor { fund_name: payload.fund_name })
+            },
+            18 => {
+                let payload = InvestorStatusPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::RevokeInvestor { fund_name: payload.fund_name })
+            },
+            19 => {
+                let payload = InvestorStatusPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::FreezeInvestor { fund_name: payload.fund_name })
+            },
+            20 => {
+                let payload = InvestorStatusPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::ThawInvestor { fund_name: payload.fund_name })
+            },
+            21 => {
+                let payload = UpdateShareMetadataPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::UpdateShareMetadata {
+                    symbol: payload.symbol,
+                    uri: payload.uri,
+                    fund_name: payload.fund_name
+                })
+            },
+            22 => {
+                let payload = CancelSubscriptionPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::CancelSubscription { fund_name: payload.fund_name })
+            }
+            23 => {
+                let payload = RejectSubscriptionPayload::try_from_slice(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Ok(Self::RejectSubscription { fund_name: payload.fund_name })
             }
             _ => Err(ProgramError::InvalidInstructionData)
         }
@@ -0,0 +1,46 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::fund_account::FundAccount;
+
+pub fn override_share_value(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_share_value: u64,
+    fund_name: String
+) -> ProgramResult {
+    let [
+        punto_xero_master,
+        manager_master,
+        risk_officer,
+        fund_account,
+        mint_account,
+        nav_history_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let nav_history_account = match nav_history_accounts {
+        [] => None,
+        [nav_history_account] => Some(nav_history_account),
+        _ => return Err(ProgramError::NotEnoughAccountKeys)
+    };
+
+    FundAccount::update_share_value(
+        program_id, 
+        punto_xero_master, 
+        manager_master, 
+        Some(risk_officer),
+        fund_account, 
+        mint_account,
+        nav_history_account,
+        new_share_value,
+        fund_name
+    )?;
+    
+    Ok(())
+}
@@ -10,6 +10,7 @@ use crate::state::share_redemption::ShareRedemption;
 pub fn process_shares_redemption(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    shares_to_process: u64,
     amount_payed: u64,
     fund_name: String,
 ) -> ProgramResult {
@@ -21,13 +22,25 @@ pub fn process_shares_redemption(
         mint_account,
         fund_vault,
         share_redemption_account,
+        investor_redemptions_account,
         investor,
         token_program,
-        system_program
+        system_program,
+        payout_accounts @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    let (payout_accounts, nav_history_account) = match payout_accounts {
+        [] => (None, None),
+        [nav_history_account] => (None, Some(nav_history_account)),
+        [cash_vault, investor_payment_account, settlement_token_program] 
+            => (Some((cash_vault, investor_payment_account, settlement_token_program)), None),
+        [cash_vault, investor_payment_account, settlement_token_program, nav_history_account] 
+            => (Some((cash_vault, investor_payment_account, settlement_token_program)), Some(nav_history_account)),
+        _ => return Err(ProgramError::NotEnoughAccountKeys)
+    };
+
     ShareRedemption::process_share_redemption(
         program_id, 
         punto_xero_master, 
@@ -35,10 +48,14 @@ pub fn process_shares_redemption(
         fund_account, 
         mint_account, 
         share_redemption_account, 
+        investor_redemptions_account,
         fund_vault,
         investor, 
         token_program, 
         system_program, 
+        payout_accounts,
+        nav_history_account,
+        shares_to_process,
         amount_payed,
         fund_name
     )?;
@@ -0,0 +1,64 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::{investor_record::InvestorRecord, subscription_request::SubscriptionRequest};
+
+pub fn process_subscription(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fund_name: String
+) -> ProgramResult {
+
+    let [
+        punto_xero_master,
+        manager_master,
+        fund_account,
+        mint_account,
+        subscription_request_account,
+        investor,
+        investor_ata,
+        system_program,
+        token_program,
+        associated_token_account_program,
+        trailing_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let (investor_record_account, trailing_accounts) = InvestorRecord::split_trailing(
+        program_id, 
+        fund_account.key, 
+        investor.key, 
+        trailing_accounts
+    );
+
+    let (investor_lots_account, nav_history_account) = match trailing_accounts {
+        [nav_history_account] => (None, nav_history_account),
+        [investor_lots_account, nav_history_account] => (Some(investor_lots_account), nav_history_account),
+        _ => return Err(ProgramError::NotEnoughAccountKeys)
+    };
+
+    SubscriptionRequest::process_subscription_request(
+        program_id, 
+        punto_xero_master, 
+        manager_master,
+        fund_account, 
+        mint_account,
+        subscription_request_account, 
+        investor, 
+        investor_ata,
+        system_program, 
+        token_program,
+        associated_token_account_program,
+        investor_lots_account,
+        nav_history_account,
+        investor_record_account,
+        fund_name
+    )?;
+
+    Ok(())
+}
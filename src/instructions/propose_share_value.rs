@@ -0,0 +1,38 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::pending_nav::PendingNav;
+
+pub fn propose_share_value(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proposed_share_value: u64,
+    fund_name: String
+) -> ProgramResult {
+    let [
+        punto_xero_master,
+        manager_master,
+        fund_account,
+        pending_nav_account,
+        system_program
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    PendingNav::propose_share_value(
+        program_id, 
+        punto_xero_master, 
+        manager_master, 
+        fund_account, 
+        pending_nav_account,
+        system_program,
+        proposed_share_value,
+        fund_name
+    )?;
+    
+    Ok(())
+}
@@ -5,7 +5,7 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
-use crate::state::share_redemption::ShareRedemption;
+use crate::state::{investor_record::InvestorRecord, share_redemption::ShareRedemption};
 
 pub fn redeem_shares(
     program_id: &Pubkey,
@@ -20,14 +20,29 @@ pub fn redeem_shares(
         mint_account,
         fund_vault,
         share_redemption_account,
+        investor_redemptions_account,
         investor,
         investor_ata,
         token_program,
         system_program,
+        investor_lots_accounts @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    let (investor_record_account, investor_lots_accounts) = InvestorRecord::split_trailing(
+        program_id, 
+        fund_account.key, 
+        investor.key, 
+        investor_lots_accounts
+    );
+
+    let investor_lots_account = match investor_lots_accounts {
+        [] => None,
+        [investor_lots_account] => Some(investor_lots_account),
+        _ => return Err(ProgramError::NotEnoughAccountKeys)
+    };
+
     ShareRedemption::create_share_redemption(
         program_id, 
         punto_xero_master, 
@@ -35,10 +50,13 @@ pub fn redeem_shares(
         mint_account, 
         fund_vault,
         share_redemption_account, 
+        investor_redemptions_account,
         investor, 
         investor_ata, 
         token_program, 
         system_program, 
+        investor_lots_account,
+        investor_record_account,
         fund_name, 
         shares_to_redeem
     )?;
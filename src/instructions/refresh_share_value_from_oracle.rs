@@ -0,0 +1,40 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::fund_account::FundAccount;
+
+pub fn refresh_share_value_from_oracle(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fund_name: String
+) -> ProgramResult {
+    let [
+        fund_account,
+        mint_account,
+        oracle_account,
+        nav_history_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let nav_history_account = match nav_history_accounts {
+        [] => None,
+        [nav_history_account] => Some(nav_history_account),
+        _ => return Err(ProgramError::NotEnoughAccountKeys)
+    };
+
+    FundAccount::refresh_share_value_from_oracle(
+        program_id, 
+        fund_account, 
+        mint_account,
+        oracle_account,
+        nav_history_account,
+        fund_name
+    )?;
+    
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::{investor_record::InvestorRecord, subscription_request::SubscriptionRequest};
+
+pub fn request_subscription(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount_in_fiat: u64,
+    fund_name: String
+) -> ProgramResult {
+
+    let [
+        punto_xero_master,
+        fund_account,
+        subscription_request_account,
+        investor,
+        system_program,
+        token_program,
+        payment_accounts @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let (investor_record_account, payment_accounts) = InvestorRecord::split_trailing(
+        program_id, 
+        fund_account.key, 
+        investor.key, 
+        payment_accounts
+    );
+
+    let payment_accounts = match payment_accounts {
+        [] => None,
+        [investor_payment_account, cash_vault, settlement_token_program] 
+            => Some((investor_payment_account, cash_vault, settlement_token_program)),
+        _ => return Err(ProgramError::NotEnoughAccountKeys)
+    };
+
+    SubscriptionRequest::create_subscription_request(
+        program_id, 
+        punto_xero_master, 
+        fund_account, 
+        subscription_request_account, 
+        investor, 
+        system_program, 
+        token_program,
+        payment_accounts,
+        investor_record_account,
+        amount_in_fiat, 
+        fund_name
+    )?;
+
+    Ok(())
+}
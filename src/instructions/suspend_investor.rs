@@ -0,0 +1,34 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::investor_record::{InvestorRecord, KycStatus};
+
+pub fn suspend_investor(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fund_name: String
+) -> ProgramResult {
+
+    let [
+        compliance_officer,
+        fund_account,
+        investor_record_account
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    InvestorRecord::set_investor_status(
+        program_id, 
+        compliance_officer, 
+        fund_account, 
+        investor_record_account, 
+        KycStatus::Suspended, 
+        fund_name
+    )?;
+
+    Ok(())
+}
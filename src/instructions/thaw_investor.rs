@@ -0,0 +1,40 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::investor_record::InvestorRecord;
+
+pub fn thaw_investor(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fund_name: String
+) -> ProgramResult {
+
+    let [
+        compliance_officer,
+        fund_account,
+        mint_account,
+        investor,
+        investor_ata,
+        token_program
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    InvestorRecord::set_account_frozen(
+        program_id, 
+        compliance_officer, 
+        fund_account, 
+        mint_account, 
+        investor, 
+        investor_ata, 
+        token_program, 
+        false, 
+        fund_name
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::fund_account::{FundAccount, FundConfig};
+
+pub fn update_fund_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    config: FundConfig,
+    fund_name: String
+) -> ProgramResult {
+    let [
+        punto_xero_master,
+        manager_master,
+        fund_account,
+        mint_account,
+        approvers @ ..
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    FundAccount::update_fund_config(
+        program_id, 
+        punto_xero_master, 
+        manager_master, 
+        fund_account, 
+        mint_account,
+        approvers,
+        config,
+        fund_name
+    )?;
+    
+    Ok(())
+}
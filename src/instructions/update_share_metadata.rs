@@ -0,0 +1,43 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    program_error::ProgramError, 
+    pubkey::Pubkey
+};
+
+use crate::state::fund_account::FundAccount;
+
+pub fn update_share_metadata(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    symbol: String,
+    uri: String,
+    fund_name: String
+) -> ProgramResult {
+
+    let [
+        punto_xero_master,
+        manager_master,
+        fund_account,
+        mint_account,
+        system_program,
+        token_program
+    ] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    FundAccount::update_share_metadata(
+        program_id, 
+        punto_xero_master, 
+        manager_master, 
+        fund_account, 
+        mint_account, 
+        system_program, 
+        token_program, 
+        symbol, 
+        uri, 
+        fund_name
+    )?;
+
+    Ok(())
+}
@@ -16,16 +16,27 @@ pub fn update_share_value(
     let [
         punto_xero_master,
         manager_master,
-        fund_account
+        fund_account,
+        mint_account,
+        nav_history_accounts @ ..
     ] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    let nav_history_account = match nav_history_accounts {
+        [] => None,
+        [nav_history_account] => Some(nav_history_account),
+        _ => return Err(ProgramError::NotEnoughAccountKeys)
+    };
+
     FundAccount::update_share_value(
         program_id, 
         punto_xero_master, 
         manager_master, 
+        None,
         fund_account, 
+        mint_account,
+        nav_history_account,
         new_share_value,
         fund_name
     )?;
@@ -10,6 +10,26 @@ use instructions::{
     update_share_value::update_share_value, 
     redeem_shares::redeem_shares,
     process_shares_redemption::process_shares_redemption,
+    update_fund_config::update_fund_config,
+    cancel_redemption::cancel_redemption,
+    accrue_fees::accrue_fees,
+    init_nav_history::init_nav_history,
+    override_share_value::override_share_value,
+    refresh_share_value_from_oracle::refresh_share_value_from_oracle,
+    propose_share_value::propose_share_value,
+    approve_share_value::approve_share_value,
+    request_subscription::request_subscription,
+    process_subscription::process_subscription,
+    escalate_redemption::escalate_redemption,
+    add_investor::add_investor,
+    suspend_investor::suspend_investor,
+    revoke_investor::revoke_investor,
+    execute_transfer_hook::execute_transfer_hook,
+    freeze_investor::freeze_investor,
+    thaw_investor::thaw_investor,
+    update_share_metadata::update_share_metadata,
+    cancel_subscription::cancel_subscription,
+    reject_subscription::reject_subscription,
     Instructions
 };
 
@@ -30,8 +50,11 @@ pub fn process_instruction(
     match Instructions::unpack(instruction_data)? {
         Instructions::InitFundAccount {
             share_value, 
+            share_decimals,
+            symbol,
+            uri,
             fund_name
-        } => init_fund(program_id, accounts, fund_name, share_value),
+        } => init_fund(program_id, accounts, fund_name, share_value, share_decimals, symbol, uri),
         Instructions::UpdateShareValue {
             new_share_value,
             fund_name
@@ -45,8 +68,75 @@ pub fn process_instruction(
             fund_name 
         } => redeem_shares(program_id, accounts, shares_to_redeem, fund_name),
         Instructions::ProcessSharesRedemption { 
+            shares_to_process,
             amount_payed,
             fund_name 
-        } => process_shares_redemption(program_id, accounts, amount_payed, fund_name),
+        } => process_shares_redemption(program_id, accounts, shares_to_process, amount_payed, fund_name),
+        Instructions::UpdateFundConfig { 
+            config, 
+            fund_name 
+        } => update_fund_config(program_id, accounts, *config, fund_name),
+        Instructions::CancelRedemption { 
+            fund_name 
+        } => cancel_redemption(program_id, accounts, fund_name),
+        Instructions::AccrueFees { 
+            fund_name 
+        } => accrue_fees(program_id, accounts, fund_name),
+        Instructions::InitNavHistory { 
+            fund_name 
+        } => init_nav_history(program_id, accounts, fund_name),
+        Instructions::OverrideShareValue {
+            new_share_value,
+            fund_name
+        } => override_share_value(program_id, accounts, new_share_value, fund_name),
+        Instructions::RefreshShareValueFromOracle { 
+            fund_name 
+        } => refresh_share_value_from_oracle(program_id, accounts, fund_name),
+        Instructions::ProposeShareValue {
+            proposed_share_value,
+            fund_name
+        } => propose_share_value(program_id, accounts, proposed_share_value, fund_name),
+        Instructions::ApproveShareValue { 
+            fund_name 
+        } => approve_share_value(program_id, accounts, fund_name),
+        Instructions::RequestSubscription {
+            amount_in_fiat,
+            fund_name
+        } => request_subscription(program_id, accounts, amount_in_fiat, fund_name),
+        Instructions::ProcessSubscription { 
+            fund_name 
+        } => process_subscription(program_id, accounts, fund_name),
+        Instructions::EscalateRedemption { 
+            fund_name 
+        } => escalate_redemption(program_id, accounts, fund_name),
+        Instructions::AddInvestor {
+            expiry_timestamp,
+            jurisdiction,
+            fund_name
+        } => add_investor(program_id, accounts, expiry_timestamp, jurisdiction, fund_name),
+        Instructions::SuspendInvestor { 
+            fund_name 
+        } => suspend_investor(program_id, accounts, fund_name),
+        Instructions::RevokeInvestor { 
+            fund_name 
+        } => revoke_investor(program_id, accounts, fund_name),
+        Instructions::ExecuteTransferHook => execute_transfer_hook(program_id, accounts),
+        Instructions::FreezeInvestor { 
+            fund_name 
+        } => freeze_investor(program_id, accounts, fund_name),
+        Instructions::ThawInvestor { 
+            fund_name 
+        } => thaw_investor(program_id, accounts, fund_name),
+        Instructions::UpdateShareMetadata {
+            symbol,
+            uri,
+            fund_name
+        } => update_share_metadata(program_id, accounts, symbol, uri, fund_name),
+        Instructions::CancelSubscription { 
+            fund_name 
+        } => cancel_subscription(program_id, accounts, fund_name),
+        Instructions::RejectSubscription { 
+            fund_name 
+        } => reject_subscription(program_id, accounts, fund_name),
     }
 }
\ No newline at end of file
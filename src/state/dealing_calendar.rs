@@ -0,0 +1,148 @@
+use solana_program::program_error::ProgramError;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use chrono::{DateTime, Datelike, NaiveDate};
+
+use crate::errors::CustomError;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum DealingFrequency {
+    // deals on every request, no calendar
+    #[default]
+    Continuous,
+    // 0 is Monday
+    Weekly { weekday: u8 },
+    // days past the end of a short month deal on its last day
+    Monthly { day: u8 },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default)]
+pub struct DealingSchedule {
+    pub frequency: DealingFrequency,
+    pub cut_off_seconds: u32,
+    pub holidays: Vec<u32>,
+    pub reject_outside_window: bool,
+}
+
+impl DealingSchedule {
+
+    pub const MAX_HOLIDAYS: usize = 16;
+
+    const SECONDS_PER_DAY: i64 = 86_400;
+
+    const MAX_LOOKAHEAD_DAYS: u32 = 400;
+
+    pub const LEN: usize = 1 + 1  // for frequency
+        + 4                       // for cut_off_seconds
+        + 4 + 4 * Self::MAX_HOLIDAYS
+        + 1;                      // for reject_outside_window
+
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        let valid_frequency = match self.frequency {
+            DealingFrequency::Continuous => true,
+            DealingFrequency::Weekly { weekday } => weekday < 7,
+            DealingFrequency::Monthly { day } => (1..=31).contains(&day),
+        };
+
+        let valid_holidays = self.holidays.len() <= Self::MAX_HOLIDAYS
+            && self.holidays.iter().all(|holiday| Self::parse_date(*holiday).is_some());
+
+        if !valid_frequency 
+            || !valid_holidays 
+            || self.cut_off_seconds as i64 >= Self::SECONDS_PER_DAY {
+            return Err(CustomError::InvalidDealingSchedule.into());
+        }
+
+        Ok(())
+    }
+
+    // cut-off of the first dealing date whose cut-off has not passed yet, or
+    // `None` for funds dealing continuously
+    pub fn next_dealing_timestamp(&self, current_timestamp: i64) -> Result<Option<i64>, ProgramError> {
+        if self.frequency == DealingFrequency::Continuous {
+            return Ok(None);
+        }
+
+        let mut date = DateTime::from_timestamp(current_timestamp, 0)
+            .ok_or::<ProgramError>(CustomError::InvalidDealingSchedule.into())?
+            .date_naive();
+
+        for _ in 0..Self::MAX_LOOKAHEAD_DAYS {
+            let cut_off_timestamp = date.and_hms_opt(0, 0, 0)
+                .ok_or::<ProgramError>(CustomError::InvalidDealingSchedule.into())?
+                .and_utc()
+                .timestamp() + self.cut_off_seconds as i64;
+
+            if self.is_dealing_date(date) && cut_off_timestamp > current_timestamp {
+                return Ok(Some(cut_off_timestamp));
+            }
+
+            date = date.succ_opt()
+                .ok_or::<ProgramError>(CustomError::InvalidDealingSchedule.into())?;
+        }
+
+        Err(CustomError::InvalidDealingSchedule.into())
+    }
+
+    // rejects anything that does not deal today before the cut-off
+    pub fn check_dealing_window(&self, current_timestamp: i64) -> Result<(), ProgramError> {
+        let Some(dealing_timestamp) = self.next_dealing_timestamp(current_timestamp)? else {
+            return Ok(());
+        };
+
+        if Self::day_of(dealing_timestamp) != Self::day_of(current_timestamp) {
+            return Err(CustomError::OutsideDealingWindow.into());
+        }
+
+        Ok(())
+    }
+
+    // dealing timestamp a queued request is tagged with
+    pub fn dealing_timestamp_for(&self, current_timestamp: i64) -> Result<i64, ProgramError> {
+        if self.reject_outside_window {
+            self.check_dealing_window(current_timestamp)?;
+        }
+
+        Ok(self.next_dealing_timestamp(current_timestamp)?.unwrap_or(current_timestamp))
+    }
+
+    fn is_dealing_date(&self, date: NaiveDate) -> bool {
+        let as_number = date.year() as u32 * 10_000 + date.month() * 100 + date.day();
+
+        if self.holidays.contains(&as_number) {
+            return false;
+        }
+
+        match self.frequency {
+            DealingFrequency::Continuous => true,
+            DealingFrequency::Weekly { weekday } 
+                => date.weekday().num_days_from_monday() == weekday as u32,
+            DealingFrequency::Monthly { day } 
+                => date.day() == (day as u32).min(Self::days_in_month(date)),
+        }
+    }
+
+    fn days_in_month(date: NaiveDate) -> u32 {
+        let (year, month) = match date.month() {
+            12 => (date.year() + 1, 1),
+            month => (date.year(), month + 1),
+        };
+
+        NaiveDate::from_ymd_opt(year, month, 1)
+            .and_then(|first_of_next_month| first_of_next_month.pred_opt())
+            .map(|last_of_month| last_of_month.day())
+            .unwrap_or(28)
+    }
+
+    fn parse_date(yyyymmdd: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(
+            (yyyymmdd / 10_000) as i32, 
+            yyyymmdd / 100 % 100, 
+            yyyymmdd % 100
+        )
+    }
+
+    fn day_of(timestamp: i64) -> i64 {
+        timestamp.div_euclid(Self::SECONDS_PER_DAY)
+    }
+}
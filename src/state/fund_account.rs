@@ -8,18 +8,23 @@ use solana_program::{
     program_pack::{IsInitialized, Pack, Sealed}, 
     pubkey::Pubkey, 
     rent::Rent, 
-    system_instruction::create_account, 
+    system_instruction::{create_account, transfer as transfer_lamports}, 
     system_program::ID as SYSTEM_PROGRAM_ID, 
     sysvar::Sysvar,
     msg,
 };
-use spl_token::{
-    ID as TOKEN_PROGRAM_ID,
+use spl_token_2022::{
+    ID as TOKEN_2022_PROGRAM_ID,
+    extension::{metadata_pointer, transfer_hook, ExtensionType},
     instruction::{initialize_mint, mint_to},
     state::Mint
 };
+use spl_token_metadata_interface::{
+    instruction::{initialize as initialize_token_metadata, update_field},
+    state::{Field, TokenMetadata}
+};
 use spl_associated_token_account::{
-    get_associated_token_address, 
+    get_associated_token_address_with_program_id, 
     instruction::create_associated_token_account,
     ID as ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID
 };
@@ -27,7 +32,158 @@ use spl_associated_token_account::{
 
 use borsh::{BorshDeserialize, BorshSerialize};
 
-use crate::utils::fixed_point_divide_checked;
+use super::{
+    dealing_calendar::DealingSchedule, 
+    investor_lots::{InvestorLots, SubscriptionLot}, 
+    investor_record::InvestorRecord, 
+    nav_history::NavHistory, 
+    price_feed::PriceFeed, 
+    share_token::{check_token_program, transfer, unpack_mint, unpack_token_account}, 
+    transfer_hook::initialize_extra_account_metas
+};
+
+use crate::{
+    errors::CustomError,
+    utils::{
+        BASIS_POINTS,
+        basis_points_of,
+        fixed_point_divide_checked, 
+        management_fee_shares, 
+        performance_fee_shares
+    }
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum PricingMode {
+    #[default]
+    Historic,
+    Forward,
+}
+
+// only enforced on Token-2022 fund mints, which carry the transfer hook
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ShareTransferPolicy {
+    #[default]
+    Allowlisted,
+    Blocked,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default)]
+pub struct FundConfig {
+    pub cancellation_cutoff_seconds: i64,
+    pub cancellation_requires_platform_signature: bool,
+    pub management_fee_bps: u16,
+    pub performance_fee_bps: u16,
+    pub performance_fee_period_seconds: i64,
+    pub subscription_fee_bps: u16,
+    pub redemption_fee_bps: u16,
+    pub platform_fee_share_bps: u16,
+    pub manager_fee_recipient: Pubkey,
+    pub platform_fee_recipient: Pubkey,
+    pub max_nav_age_seconds: i64,
+    pub max_share_value_change_bps: u16,
+    pub risk_officer: Pubkey,
+    pub oracle: Pubkey,
+    pub oracle_program: Pubkey,
+    pub max_oracle_confidence_bps: u16,
+    pub max_oracle_age_seconds: i64,
+    pub nav_attesters: Vec<Pubkey>,
+    pub nav_attestation_threshold: u8,
+    pub nav_attestation_window_seconds: i64,
+    pub subscription_pricing: PricingMode,
+    pub redemption_pricing: PricingMode,
+    pub dealing_schedule: DealingSchedule,
+    pub redemption_notice_seconds: i64,
+    pub settlement_deadline_seconds: i64,
+    pub redemption_gate_bps: u16,
+    pub redemption_gate_period_seconds: i64,
+    pub lock_up_seconds: i64,
+    pub early_redemption_fee_bps: u16,
+    pub min_initial_investment: u64,
+    pub min_top_up: u64,
+    pub min_redemption_shares: u64,
+    pub max_holding_shares: u64,
+    pub compliance_officer: Pubkey,
+    pub kyc_required: bool,
+    pub share_transfer_policy: ShareTransferPolicy,
+}
+
+impl FundConfig {
+    pub const MAX_NAV_ATTESTERS: usize = 5;
+
+    pub const LEN: usize = 8 // for cancellation_cutoff_seconds
+        + 1                  // for cancellation_requires_platform_signature
+        + 2                  // for management_fee_bps
+        + 2                  // for performance_fee_bps
+        + 8                  // for performance_fee_period_seconds
+        + 2                  // for subscription_fee_bps
+        + 2                  // for redemption_fee_bps
+        + 2                  // for platform_fee_share_bps
+        + 32                 // for manager_fee_recipient
+        + 32                 // for platform_fee_recipient
+        + 8                  // for max_nav_age_seconds
+        + 2                  // for max_share_value_change_bps
+        + 32                 // for risk_officer
+        + 32                 // for oracle
+        + 32                 // for oracle_program
+        + 2                  // for max_oracle_confidence_bps
+        + 8                  // for max_oracle_age_seconds
+        + 4 + 32 * Self::MAX_NAV_ATTESTERS
+        + 1                  // for nav_attestation_threshold
+        + 8                  // for nav_attestation_window_seconds
+        + 1                  // for subscription_pricing
+        + 1                  // for redemption_pricing
+        + DealingSchedule::LEN
+        + 8                  // for redemption_notice_seconds
+        + 8                  // for settlement_deadline_seconds
+        + 2                  // for redemption_gate_bps
+        + 8                  // for redemption_gate_period_seconds
+        + 8                  // for lock_up_seconds
+        + 2                  // for early_redemption_fee_bps
+        + 8                  // for min_initial_investment
+        + 8                  // for min_top_up
+        + 8                  // for min_redemption_shares
+        + 8                  // for max_holding_shares
+        + 32                 // for compliance_officer
+        + 1                  // for kyc_required
+        + 1;                 // for share_transfer_policy
+
+    // signers among the current attesters must meet the current threshold
+    pub fn check_attester_approval(&self, attesters: &[AccountInfo]) -> ProgramResult {
+        let mut approvals: Vec<&Pubkey> = Vec::new();
+
+        for attester in attesters {
+            if !attester.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            if !self.nav_attesters.contains(attester.key) {
+                return Err(CustomError::NotANavAttester.into());
+            }
+
+            if approvals.contains(&attester.key) {
+                return Err(CustomError::DuplicateNavAttestation.into());
+            }
+
+            approvals.push(attester.key);
+        }
+
+        if approvals.len() < self.nav_attestation_threshold as usize {
+            return Err(CustomError::NavAttesterApprovalRequired.into());
+        }
+
+        Ok(())
+    }
+
+    // a configured risk officer must sign off on changes to their own controls
+    pub fn check_risk_officer_approval(&self, approvers: &[AccountInfo]) -> ProgramResult {
+        if !approvers.iter().any(|approver| approver.is_signer && *approver.key == self.risk_officer) {
+            return Err(CustomError::RiskOfficerApprovalRequired.into());
+        }
+
+        Ok(())
+    }
+}
  
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct FundAccount {
@@ -37,9 +193,26 @@ pub struct FundAccount {
     pub manager_master_pubkey: Pubkey,
     pub fund_mint: Pubkey,
     pub mint_bump_seed: u8,
+    pub share_decimals: u8,
     pub fund_vault: Pubkey,
     pub share_value: u64,
     pub share_value_update: i64,
+    pub settlement_mint: Option<Pubkey>,
+    pub cash_vault: Option<Pubkey>,
+    pub settlement_token_program: Option<Pubkey>,
+    pub config: FundConfig,
+    pub accrued_management_fee_shares: u64,
+    pub management_fee_timestamp: i64,
+    pub high_water_mark: u64,
+    pub accrued_performance_fee_shares: u64,
+    pub performance_fee_timestamp: i64,
+    pub accrued_manager_fee_shares: u64,
+    pub accrued_platform_fee_shares: u64,
+    pub nav_history: Option<Pubkey>,
+    pub next_subscription_request_id: u64,
+    pub gate_period_start: i64,
+    pub gate_period_supply: u64,
+    pub gate_redeemed_shares: u64,
     pub fund_name: String
 }
 
@@ -53,6 +226,9 @@ impl IsInitialized for FundAccount {
 
 impl FundAccount {
 
+    // keeps fiat amounts scaled by the share decimals within u64
+    pub const MAX_SHARE_DECIMALS: u8 = 9;
+
     fn get_space(name: &str) -> usize {
         return 4
             + 1      // for is_initialized
@@ -61,9 +237,26 @@ impl FundAccount {
             + 32     // for manager_master_pubkey
             + 32
             + 1
+            + 1      // for share_decimals
             + 32
             + 8      // for share_value
             + 8      // for share_value_update 
+            + 33     // for settlement_mint
+            + 33     // for cash_vault
+            + 33     // for settlement_token_program
+            + FundConfig::LEN
+            + 8      // for accrued_management_fee_shares
+            + 8      // for management_fee_timestamp
+            + 8      // for high_water_mark
+            + 8      // for accrued_performance_fee_shares
+            + 8      // for performance_fee_timestamp
+            + 8      // for accrued_manager_fee_shares
+            + 8      // for accrued_platform_fee_shares
+            + 33     // for nav_history
+            + 8      // for next_subscription_request_id
+            + 8      // for gate_period_start
+            + 8      // for gate_period_supply
+            + 8      // for gate_redeemed_shares
             + name.len()
     }
 
@@ -78,7 +271,12 @@ impl FundAccount {
         token_program: &AccountInfo<'a>,
         associated_token_account_program: &AccountInfo<'a>,
         rent_sysvar: &AccountInfo<'a>,
+        settlement_accounts: Option<(&AccountInfo<'a>, &AccountInfo<'a>, &AccountInfo<'a>)>,
+        extra_account_metas_account: Option<&AccountInfo<'a>>,
         share_value: u64,
+        share_decimals: u8,
+        symbol: String,
+        uri: String,
         fund_name: &str
     ) -> ProgramResult {
 
@@ -86,9 +284,7 @@ impl FundAccount {
             return Err(ProgramError::InvalidAccountData)
         }
 
-        if *token_program.key != TOKEN_PROGRAM_ID {
-            return Err(ProgramError::InvalidAccountData)
-        }
+        check_token_program(token_program)?;
 
         if *associated_token_account_program.key != ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID {
             return Err(ProgramError::InvalidAccountData)
@@ -102,6 +298,19 @@ impl FundAccount {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
+        if share_value == 0 {
+            return Err(CustomError::InvalidShareValue.into());
+        }
+
+        if share_decimals > Self::MAX_SHARE_DECIMALS {
+            return Err(CustomError::InvalidShareDecimals.into());
+        }
+
+        // classic SPL Token mints have nowhere to keep a symbol or uri
+        if *token_program.key != TOKEN_2022_PROGRAM_ID && (!symbol.is_empty() || !uri.is_empty()) {
+            return Err(CustomError::MintHasNoMetadata.into());
+        }
+
         let space = Self::get_space(&fund_name);
         let rent = Rent::get()?.minimum_balance(space);
         let current_timestamp = Clock::get()?.unix_timestamp;
@@ -124,15 +333,38 @@ impl FundAccount {
             return Err(ProgramError::InvalidAccountData)
         }
 
-        let fund_vault_pda = get_associated_token_address(
+        let fund_vault_pda = get_associated_token_address_with_program_id(
             &fund_account.key, 
-            &mint_account.key
+            &mint_account.key, 
+            token_program.key
         );
 
         if fund_vault_pda != *fund_vault.key {
             return Err(ProgramError::InvalidAccountData)
         }
 
+        if let Some((settlement_mint, cash_vault, settlement_token_program)) = settlement_accounts {
+            // cash can live on a different token program than the shares, e.g.
+            // classic USDC for a Token-2022 fund mint
+            check_token_program(settlement_token_program)?;
+
+            if *settlement_mint.owner != *settlement_token_program.key {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            unpack_mint(settlement_mint)?;
+
+            let cash_vault_pda = get_associated_token_address_with_program_id(
+                fund_account.key, 
+                settlement_mint.key, 
+                settlement_token_program.key
+            );
+
+            if cash_vault_pda != *cash_vault.key {
+                return Err(ProgramError::InvalidAccountData)
+            }
+        }
+
         invoke_signed(
             &create_account(
                 punto_xero.key, 
@@ -169,15 +401,49 @@ impl FundAccount {
         account_data.manager_master_pubkey = *manager.key;
         account_data.fund_mint = *mint_account.key;
         account_data.mint_bump_seed = mint_bump;
+        account_data.share_decimals = share_decimals;
         account_data.fund_vault = *fund_vault.key;
         account_data.share_value = share_value;
         account_data.share_value_update = current_timestamp;
+        account_data.settlement_mint = settlement_accounts.map(|(mint, _, _)| *mint.key);
+        account_data.cash_vault = settlement_accounts.map(|(_, vault, _)| *vault.key);
+        account_data.settlement_token_program = settlement_accounts.map(|(_, _, program)| *program.key);
+        account_data.management_fee_timestamp = current_timestamp;
+        account_data.high_water_mark = share_value;
+        account_data.performance_fee_timestamp = current_timestamp;
         account_data.fund_name = fund_name.to_owned();
 
         account_data.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
 
-        let mint_space = Mint::LEN;
-        let rent_lamports = Rent::get()?.minimum_balance(mint_space);
+        // Token-2022 fund mints carry a transfer hook back into this program
+        // and their own name, symbol and uri
+        let token_2022_mint = *token_program.key == TOKEN_2022_PROGRAM_ID;
+
+        if token_2022_mint != extra_account_metas_account.is_some() {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        let share_metadata = TokenMetadata {
+            name: fund_name.to_owned(),
+            symbol,
+            uri,
+            ..TokenMetadata::default()
+        };
+
+        let (mint_space, rent_lamports) = if token_2022_mint {
+            let mint_space = ExtensionType::try_calculate_account_len::<Mint>(&[
+                ExtensionType::TransferHook, 
+                ExtensionType::MetadataPointer
+            ])?;
+
+            // token metadata is appended after the mint is initialized, so the
+            // rent for it is paid upfront
+            let full_space = mint_space + share_metadata.tlv_size_of()?;
+
+            (mint_space, Rent::get()?.minimum_balance(full_space))
+        } else {
+            (Mint::LEN, Rent::get()?.minimum_balance(Mint::LEN))
+        };
 
         let create_account_ix = create_account(
             &punto_xero.key, 
@@ -202,12 +468,38 @@ impl FundAccount {
             ]]
         )?;
 
+        if token_2022_mint {
+            let init_transfer_hook_ix = transfer_hook::instruction::initialize(
+                token_program.key, 
+                mint_account.key, 
+                Some(*fund_account.key), 
+                Some(*program_id)
+            )?;
+
+            let init_metadata_pointer_ix = metadata_pointer::instruction::initialize(
+                token_program.key, 
+                mint_account.key, 
+                Some(*fund_account.key), 
+                Some(*mint_account.key)
+            )?;
+
+            for extension_ix in [init_transfer_hook_ix, init_metadata_pointer_ix] {
+                invoke(
+                    &extension_ix, 
+                    &[
+                        token_program.clone(),
+                        mint_account.clone()
+                    ]
+                )?;
+            }
+        }
+
         let create_mint_ix = initialize_mint(
             &token_program.key, 
             &mint_account.key, 
             &fund_account.key, 
-            None, 
-            6
+            Some(&fund_account.key), 
+            share_decimals
         )?;
 
         invoke_signed(
@@ -225,6 +517,33 @@ impl FundAccount {
             ]]
         )?;
 
+        if token_2022_mint {
+            let init_metadata_ix = initialize_token_metadata(
+                token_program.key, 
+                mint_account.key, 
+                fund_account.key, 
+                mint_account.key, 
+                fund_account.key, 
+                share_metadata.name, 
+                share_metadata.symbol, 
+                share_metadata.uri
+            );
+
+            invoke_signed(
+                &init_metadata_ix, 
+                &[
+                    token_program.clone(),
+                    mint_account.clone(),
+                    fund_account.clone()
+                ], 
+                &[&[
+                    b"fund_account",
+                    fund_name.as_bytes(),
+                    &[bump_seed]
+                ]]
+            )?;
+        }
+
         msg!("created mint!");
 
         let create_vault_ix = create_associated_token_account(
@@ -254,6 +573,43 @@ impl FundAccount {
             ]
         )?;
 
+        if let Some((settlement_mint, cash_vault, settlement_token_program)) = settlement_accounts {
+            let create_cash_vault_ix = create_associated_token_account(
+                punto_xero.key, 
+                fund_account.key, 
+                settlement_mint.key, 
+                settlement_token_program.key
+            );
+
+            invoke(
+                &create_cash_vault_ix, 
+                &[
+                    settlement_mint.clone(),
+                    cash_vault.clone(),
+                    punto_xero.clone(),
+                    fund_account.clone(),
+                    settlement_token_program.clone(),
+                    system_program.clone(),
+                    associated_token_account_program.clone(),
+                ]
+            )?;
+
+            msg!("created cash vault!");
+        }
+
+        if let Some(extra_account_metas_account) = extra_account_metas_account {
+            initialize_extra_account_metas(
+                program_id, 
+                punto_xero, 
+                fund_account, 
+                mint_account, 
+                extra_account_metas_account, 
+                system_program
+            )?;
+
+            msg!("initialized transfer hook!");
+        }
+
         Ok(())
     }
 
@@ -261,7 +617,10 @@ impl FundAccount {
         program_id: &Pubkey,
         punto_xero: &AccountInfo<'a>,
         manager: &AccountInfo<'a>,
+        risk_officer: Option<&AccountInfo<'a>>,
         fund_account: &AccountInfo<'a>,
+        mint_account: &AccountInfo<'a>,
+        nav_history_account: Option<&AccountInfo<'a>>,
         new_share_value: u64,
         fund_name: String
     ) -> ProgramResult {
@@ -273,6 +632,10 @@ impl FundAccount {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
+        if new_share_value == 0 {
+            return Err(CustomError::InvalidShareValue.into());
+        }
+
         if fund_account.owner != program_id {
             return Err(ProgramError::IllegalOwner);
         }
@@ -298,53 +661,191 @@ impl FundAccount {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        account_data.share_value = new_share_value;
-        account_data.share_value_update = Clock::get()?.unix_timestamp;
+        if account_data.fund_mint != *mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        match risk_officer {
+            Some(risk_officer) => {
+                if !risk_officer.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+
+                if account_data.config.risk_officer != *risk_officer.key {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+
+                // the override only lifts the change band, attested funds
+                // still go through their attesters
+                if account_data.config.nav_attestation_threshold > 0 {
+                    return Err(CustomError::ShareValueRequiresAttestation.into());
+                }
+
+                msg!("risk officer override of share value band");
+            },
+            None => {
+                if account_data.config.oracle != Pubkey::default() {
+                    return Err(CustomError::ShareValueSourcedFromOracle.into());
+                }
+
+                if account_data.config.nav_attestation_threshold > 0 {
+                    return Err(CustomError::ShareValueRequiresAttestation.into());
+                }
+
+                account_data.check_share_value_change(new_share_value)?
+            }
+        }
+
+        account_data.set_share_value(
+            program_id, 
+            mint_account, 
+            nav_history_account, 
+            new_share_value, 
+            Clock::get()?.unix_timestamp
+        )?;
 
         account_data.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
 
         Ok(())
     }
 
-    pub fn buy_fund_shares<'a>(
+    pub fn refresh_share_value_from_oracle(
+        program_id: &Pubkey,
+        fund_account: &AccountInfo,
+        mint_account: &AccountInfo,
+        oracle_account: &AccountInfo,
+        nav_history_account: Option<&AccountInfo>,
+        fund_name: String
+    ) -> ProgramResult {
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[account_data.bump_seed]], 
+            program_id
+        )?;
+
+        if pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if account_data.fund_mint != *mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if account_data.config.nav_attestation_threshold > 0 {
+            return Err(CustomError::ShareValueRequiresAttestation.into());
+        }
+
+        let price_feed = PriceFeed::load(
+            oracle_account, 
+            &account_data.config.oracle, 
+            &account_data.config.oracle_program
+        )?;
+
+        let new_share_value = price_feed.check(
+            account_data.config.max_oracle_confidence_bps,
+            account_data.config.max_oracle_age_seconds,
+            Clock::get()?.unix_timestamp
+        )?;
+
+        // the nav is only as fresh as the price behind it, so an already
+        // applied feed must not re-stamp it
+        if price_feed.publish_timestamp <= account_data.share_value_update {
+            msg!("oracle price already applied");
+
+            return Ok(());
+        }
+
+        account_data.check_share_value_change(new_share_value)?;
+
+        account_data.set_share_value(
+            program_id, 
+            mint_account, 
+            nav_history_account, 
+            new_share_value, 
+            price_feed.publish_timestamp
+        )?;
+
+        account_data.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
+
+        msg!("share value refreshed from oracle: {}", new_share_value);
+
+        Ok(())
+    }
+
+    pub fn set_share_value(
+        &mut self,
+        program_id: &Pubkey,
+        mint_account: &AccountInfo,
+        nav_history_account: Option<&AccountInfo>,
+        new_share_value: u64,
+        valuation_timestamp: i64
+    ) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        self.accrue_management_fee(mint_account, current_timestamp)?;
+
+        self.share_value = new_share_value;
+        self.share_value_update = valuation_timestamp;
+
+        self.crystallise_performance_fee(mint_account, current_timestamp)?;
+
+        NavHistory::record(program_id, self, nav_history_account)?;
+
+        Ok(())
+    }
+
+    pub fn check_share_value_change(&self, new_share_value: u64) -> ProgramResult {
+        let max_change_bps = self.config.max_share_value_change_bps;
+
+        if max_change_bps == 0 {
+            return Ok(());
+        }
+
+        let change = self.share_value.abs_diff(new_share_value) as u128;
+        let max_change = self.share_value as u128 * max_change_bps as u128;
+
+        if change * BASIS_POINTS as u128 > max_change {
+            return Err(CustomError::ShareValueChangeTooLarge.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn update_fund_config<'a>(
         program_id: &Pubkey,
         punto_xero: &AccountInfo<'a>,
+        manager: &AccountInfo<'a>,
         fund_account: &AccountInfo<'a>,
         mint_account: &AccountInfo<'a>,
-        buyer: &AccountInfo<'a>,
-        buyer_ata: &AccountInfo<'a>,
-        system_program: &AccountInfo<'a>,
-        token_program: &AccountInfo<'a>,
-        associated_token_account_program: &AccountInfo<'a>,
-        amount_in_fiat: u64,
-        fund_name: &str
+        approvers: &[AccountInfo<'a>],
+        config: FundConfig,
+        fund_name: String
     ) -> ProgramResult {
         if !punto_xero.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
-
-        if !buyer.is_signer {
+        
+        if !manager.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        if *token_program.key != TOKEN_PROGRAM_ID {
-            return Err(ProgramError::InvalidAccountData)
-        }
-
-        if *system_program.key != SYSTEM_PROGRAM_ID {
-            return Err(ProgramError::InvalidAccountData)
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
         }
 
-        if *associated_token_account_program.key != ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID {
-            return Err(ProgramError::InvalidAccountData)
-        }
-        
-        let fund_account_data = try_from_slice_unchecked::<FundAccount>(
+        let mut account_data = try_from_slice_unchecked::<FundAccount>(
             &fund_account.data.borrow()[..]
         )?;
 
         let pda = Pubkey::create_program_address(
-            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]], 
+            &[b"fund_account", fund_name.as_bytes(), &[account_data.bump_seed]], 
             program_id
         )?;
 
@@ -352,82 +853,766 @@ impl FundAccount {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        if fund_account_data.punto_xero_master_pubkey != *punto_xero.key {
+        if account_data.punto_xero_master_pubkey != *punto_xero.key {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        if fund_account_data.fund_mint != *mint_account.key {
+        if account_data.manager_master_pubkey != *manager.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if account_data.fund_mint != *mint_account.key {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        let current_share_value = fund_account_data.share_value;
+        if config.nav_attesters.len() > FundConfig::MAX_NAV_ATTESTERS
+            || config.nav_attestation_threshold as usize > config.nav_attesters.len() {
+            return Err(CustomError::InvalidNavAttesters.into());
+        }
 
-        let shares_to_buy = fixed_point_divide_checked(
-            amount_in_fiat, 
-            current_share_value
-        )?;
+        // the manager cannot swap out or switch off the attesters on their own
+        let current_config = &account_data.config;
 
-        let ata_address = get_associated_token_address(
-            &buyer.key, 
-            &mint_account.key
-        );
+        if current_config.nav_attestation_threshold > 0
+            && (current_config.nav_attesters != config.nav_attesters
+                || current_config.nav_attestation_threshold != config.nav_attestation_threshold) {
+            let attesters: Vec<AccountInfo<'a>> = approvers.iter()
+                .filter(|approver| *approver.key != current_config.risk_officer
+                    || current_config.nav_attesters.contains(approver.key))
+                .cloned()
+                .collect();
 
-        if ata_address != *buyer_ata.key {
-            return Err(ProgramError::InvalidAccountData);
+            current_config.check_attester_approval(&attesters)?;
         }
 
-        if buyer_ata.lamports() == 0 {
+        // nor replace the risk officer or widen the share value band
+        if current_config.risk_officer != Pubkey::default()
+            && (current_config.risk_officer != config.risk_officer
+                || current_config.max_share_value_change_bps != config.max_share_value_change_bps) {
+            current_config.check_risk_officer_approval(approvers)?;
+        }
 
-            let create_ata_ix = create_associated_token_account(
-                punto_xero.key, 
-                buyer.key, 
-                mint_account.key, 
-                token_program.key
-            );
+        config.dealing_schedule.validate()?;
 
-            invoke(
-                &create_ata_ix, 
-                &[
-                    mint_account.clone(),
-                    buyer_ata.clone(),
-                    punto_xero.clone(),
-                    buyer.clone(),
-                    token_program.clone(),
-                    system_program.clone(),
-                    associated_token_account_program.clone(),
-                ]
-            )?;
+        let fee_bps = [
+            config.performance_fee_bps,
+            config.subscription_fee_bps,
+            config.redemption_fee_bps,
+            config.platform_fee_share_bps,
+            config.early_redemption_fee_bps,
+        ];
 
+        if fee_bps.iter().any(|bps| *bps as u64 > BASIS_POINTS) {
+            return Err(CustomError::InvalidFeeBasisPoints.into());
         }
 
-        let mint_shares_ix = mint_to(
-            &token_program.key, 
-            &mint_account.key, 
-            &buyer_ata.key, 
-            &fund_account.key, 
-            &[&fund_account.key], 
-            shares_to_buy
-        )?;
+        if config.redemption_gate_bps as u64 > BASIS_POINTS
+            || (config.redemption_gate_bps > 0 && config.redemption_gate_period_seconds <= 0) {
+            return Err(CustomError::InvalidRedemptionGate.into());
+        }
 
-        invoke_signed(
-            &mint_shares_ix, 
-            &[
-                token_program.clone(),
-                mint_account.clone(),
-                buyer_ata.clone(),
-                buyer.clone(),
-                fund_account.clone()
-            ], 
-            &[
-                &[
-                    b"fund_account",
-                    fund_name.as_bytes(),
-                    &[fund_account_data.bump_seed]
-                ],
-            ]
-        )?;
-        
-        Ok(())
-    }
-    
-}
\ No newline at end of file
+        account_data.accrue_management_fee(mint_account, Clock::get()?.unix_timestamp)?;
+
+        account_data.config = config;
+
+        account_data.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    pub fn update_share_metadata<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        manager: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        mint_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        symbol: String,
+        uri: String,
+        fund_name: String
+    ) -> ProgramResult {
+        if !punto_xero.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        
+        if !manager.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if *system_program.key != SYSTEM_PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[account_data.bump_seed]], 
+            program_id
+        )?;
+
+        if pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if account_data.punto_xero_master_pubkey != *punto_xero.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if account_data.manager_master_pubkey != *manager.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if account_data.fund_mint != *mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if *token_program.key != TOKEN_2022_PROGRAM_ID || *mint_account.owner != TOKEN_2022_PROGRAM_ID {
+            return Err(CustomError::MintHasNoMetadata.into());
+        }
+
+        for (field, value) in [(Field::Symbol, symbol), (Field::Uri, uri)] {
+            invoke_signed(
+                &update_field(
+                    token_program.key, 
+                    mint_account.key, 
+                    fund_account.key, 
+                    field, 
+                    value
+                ), 
+                &[
+                    token_program.clone(),
+                    mint_account.clone(),
+                    fund_account.clone()
+                ], 
+                &[&[
+                    b"fund_account",
+                    fund_name.as_bytes(),
+                    &[account_data.bump_seed]
+                ]]
+            )?;
+        }
+
+        // a longer symbol or uri grows the mint, which must stay rent exempt
+        let minimum_balance = Rent::get()?.minimum_balance(mint_account.data_len());
+
+        if mint_account.lamports() < minimum_balance {
+            invoke(
+                &transfer_lamports(
+                    punto_xero.key, 
+                    mint_account.key, 
+                    minimum_balance - mint_account.lamports()
+                ), 
+                &[
+                    punto_xero.clone(),
+                    mint_account.clone(),
+                    system_program.clone()
+                ]
+            )?;
+        }
+
+        msg!("updated share metadata!");
+
+        Ok(())
+    }
+
+    pub fn buy_fund_shares<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        mint_account: &AccountInfo<'a>,
+        buyer: &AccountInfo<'a>,
+        buyer_ata: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        associated_token_account_program: &AccountInfo<'a>,
+        payment_accounts: Option<(&AccountInfo<'a>, &AccountInfo<'a>, &AccountInfo<'a>)>,
+        investor_lots_account: Option<&AccountInfo<'a>>,
+        investor_record_account: Option<&AccountInfo<'a>>,
+        amount_in_fiat: u64,
+        fund_name: &str
+    ) -> ProgramResult {
+        if !punto_xero.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !buyer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        check_token_program(token_program)?;
+
+        if *system_program.key != SYSTEM_PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        if *associated_token_account_program.key != ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountData)
+        }
+        
+        let mut fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]], 
+            program_id
+        )?;
+
+        if pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.punto_xero_master_pubkey != *punto_xero.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account_data.fund_mint != *mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.config.subscription_pricing == PricingMode::Forward {
+            return Err(CustomError::ForwardPricingRequired.into());
+        }
+
+        InvestorRecord::check_investor(
+            program_id, 
+            fund_account.key, 
+            &fund_account_data, 
+            investor_record_account, 
+            buyer.key, 
+            Clock::get()?.unix_timestamp
+        )?;
+
+        fund_account_data.config.dealing_schedule.check_dealing_window(Clock::get()?.unix_timestamp)?;
+
+        fund_account_data.accrue_management_fee(mint_account, Clock::get()?.unix_timestamp)?;
+
+        fund_account_data.check_share_value_freshness(Clock::get()?.unix_timestamp)?;
+
+        if let Some(settlement_mint) = fund_account_data.settlement_mint {
+            let Some((buyer_payment_account, cash_vault, settlement_token_program)) = payment_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            fund_account_data.check_settlement_token_program(settlement_token_program)?;
+
+            let buyer_payment_address = get_associated_token_address_with_program_id(
+                buyer.key, 
+                &settlement_mint, 
+                settlement_token_program.key
+            );
+
+            if buyer_payment_address != *buyer_payment_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if fund_account_data.cash_vault != Some(*cash_vault.key) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let payment_ix = transfer(
+                settlement_token_program.key, 
+                buyer_payment_account.key, 
+                cash_vault.key, 
+                buyer.key, 
+                &[buyer.key], 
+                amount_in_fiat
+            )?;
+
+            invoke(
+                &payment_ix, 
+                &[
+                    settlement_token_program.clone(),
+                    buyer_payment_account.clone(),
+                    cash_vault.clone(),
+                    buyer.clone()
+                ]
+            )?;
+        }
+
+        fund_account_data.issue_shares(
+            program_id,
+            punto_xero, 
+            fund_account, 
+            mint_account, 
+            buyer, 
+            buyer_ata, 
+            system_program, 
+            token_program, 
+            associated_token_account_program, 
+            investor_lots_account,
+            amount_in_fiat, 
+            fund_account_data.share_value,
+            fund_name
+        )?;
+        
+        Ok(())
+    }
+
+    pub fn issue_shares<'a>(
+        &mut self,
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        mint_account: &AccountInfo<'a>,
+        investor: &AccountInfo<'a>,
+        investor_ata: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        associated_token_account_program: &AccountInfo<'a>,
+        investor_lots_account: Option<&AccountInfo<'a>>,
+        amount_in_fiat: u64,
+        share_value: u64,
+        fund_name: &str
+    ) -> Result<u64, ProgramError> {
+        let current_holding = if investor_ata.lamports() == 0 {
+            0
+        } else {
+            unpack_token_account(investor_ata)?.amount
+        };
+
+        if current_holding == 0 && amount_in_fiat < self.config.min_initial_investment {
+            return Err(CustomError::BelowMinimumInitialInvestment.into());
+        }
+
+        if current_holding > 0 && amount_in_fiat < self.config.min_top_up {
+            return Err(CustomError::BelowMinimumTopUp.into());
+        }
+
+        let gross_shares = fixed_point_divide_checked(
+            amount_in_fiat, 
+            share_value, 
+            self.share_decimals
+        )?;
+
+        let fee_shares = basis_points_of(
+            gross_shares, 
+            self.config.subscription_fee_bps
+        )?;
+
+        let shares_to_buy = gross_shares.checked_sub(fee_shares)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+        if shares_to_buy == 0 {
+            return Err(CustomError::ZeroSharesIssued.into());
+        }
+
+        let max_holding_shares = self.config.max_holding_shares;
+
+        if max_holding_shares > 0 
+            && current_holding.saturating_add(shares_to_buy) > max_holding_shares {
+            return Err(CustomError::AboveMaximumHolding.into());
+        }
+
+        self.accrue_transaction_fee(fee_shares)?;
+
+        if self.config.lock_up_seconds > 0 {
+            let Some(investor_lots_account) = investor_lots_account else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            let mut investor_lots = InvestorLots::load_or_create(
+                program_id, 
+                punto_xero, 
+                investor_lots_account, 
+                fund_account, 
+                investor, 
+                system_program, 
+                fund_name
+            )?;
+
+            investor_lots.add_lot(SubscriptionLot {
+                timestamp: Clock::get()?.unix_timestamp,
+                shares: shares_to_buy,
+            })?;
+
+            investor_lots.save(investor_lots_account)?;
+        }
+
+        let ata_address = get_associated_token_address_with_program_id(
+            &investor.key, 
+            &mint_account.key, 
+            token_program.key
+        );
+
+        if ata_address != *investor_ata.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if investor_ata.lamports() == 0 {
+
+            let create_ata_ix = create_associated_token_account(
+                punto_xero.key, 
+                investor.key, 
+                mint_account.key, 
+                token_program.key
+            );
+
+            invoke(
+                &create_ata_ix, 
+                &[
+                    mint_account.clone(),
+                    investor_ata.clone(),
+                    punto_xero.clone(),
+                    investor.clone(),
+                    token_program.clone(),
+                    system_program.clone(),
+                    associated_token_account_program.clone(),
+                ]
+            )?;
+
+        }
+
+        self.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
+
+        let mint_shares_ix = mint_to(
+            &token_program.key, 
+            &mint_account.key, 
+            &investor_ata.key, 
+            &fund_account.key, 
+            &[&fund_account.key], 
+            shares_to_buy
+        )?;
+
+        invoke_signed(
+            &mint_shares_ix, 
+            &[
+                token_program.clone(),
+                mint_account.clone(),
+                investor_ata.clone(),
+                investor.clone(),
+                fund_account.clone()
+            ], 
+            &[
+                &[
+                    b"fund_account",
+                    fund_name.as_bytes(),
+                    &[self.bump_seed]
+                ],
+            ]
+        )?;
+
+        Ok(shares_to_buy)
+    }
+
+    pub fn accrue_management_fee(
+        &mut self,
+        mint_account: &AccountInfo,
+        current_timestamp: i64
+    ) -> ProgramResult {
+        let elapsed_seconds = current_timestamp
+            .saturating_sub(self.management_fee_timestamp)
+            .max(0) as u64;
+
+        if self.config.management_fee_bps > 0 && elapsed_seconds > 0 {
+            let supply = unpack_mint(mint_account)?.supply;
+
+            let fee_shares = management_fee_shares(
+                supply, 
+                self.config.management_fee_bps, 
+                elapsed_seconds
+            )?;
+
+            self.accrued_management_fee_shares = self.accrued_management_fee_shares
+                .checked_add(fee_shares)
+                .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+        }
+
+        self.management_fee_timestamp = current_timestamp;
+
+        Ok(())
+    }
+
+    // caps a fill to what is left of the current gate period, leaving the rest
+    // of the request open for a later period
+    pub fn gate_redemption(
+        &mut self,
+        mint_account: &AccountInfo,
+        shares_to_process: u64,
+        current_timestamp: i64
+    ) -> Result<u64, ProgramError> {
+        let gate_bps = self.config.redemption_gate_bps;
+
+        if gate_bps == 0 {
+            return Ok(shares_to_process);
+        }
+
+        let period_end = self.gate_period_start
+            .checked_add(self.config.redemption_gate_period_seconds)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+        if self.gate_period_start == 0 || current_timestamp >= period_end {
+            self.gate_period_start = current_timestamp;
+            self.gate_period_supply = unpack_mint(mint_account)?.supply;
+            self.gate_redeemed_shares = 0;
+        }
+
+        let remaining_shares = basis_points_of(self.gate_period_supply, gate_bps)?
+            .saturating_sub(self.gate_redeemed_shares);
+
+        if remaining_shares == 0 {
+            return Err(CustomError::RedemptionGateClosed.into());
+        }
+
+        let gated_shares = shares_to_process.min(remaining_shares);
+
+        self.gate_redeemed_shares = self.gate_redeemed_shares
+            .checked_add(gated_shares)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+        if gated_shares < shares_to_process {
+            msg!("redemption gate: {} of {} shares processed", gated_shares, shares_to_process);
+        }
+
+        Ok(gated_shares)
+    }
+
+    pub fn crystallise_performance_fee(
+        &mut self,
+        mint_account: &AccountInfo,
+        current_timestamp: i64
+    ) -> ProgramResult {
+        if self.config.performance_fee_bps == 0 {
+            return Ok(());
+        }
+
+        let period_seconds = self.config.performance_fee_period_seconds;
+
+        if period_seconds > 0 && current_timestamp < self.performance_fee_timestamp.saturating_add(period_seconds) {
+            return Ok(());
+        }
+
+        if self.share_value > self.high_water_mark {
+            let supply = unpack_mint(mint_account)?.supply;
+
+            let fee_shares = performance_fee_shares(
+                supply, 
+                self.share_value, 
+                self.high_water_mark, 
+                self.config.performance_fee_bps
+            )?;
+
+            self.accrued_performance_fee_shares = self.accrued_performance_fee_shares
+                .checked_add(fee_shares)
+                .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+            self.high_water_mark = self.share_value;
+
+            msg!("crystallised {} performance fee shares", fee_shares);
+        }
+
+        self.performance_fee_timestamp = current_timestamp;
+
+        Ok(())
+    }
+
+    pub fn accrue_fees<'a>(
+        program_id: &Pubkey,
+        payer: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        mint_account: &AccountInfo<'a>,
+        manager_fee_recipient: &AccountInfo<'a>,
+        manager_fee_ata: &AccountInfo<'a>,
+        platform_fee_recipient: &AccountInfo<'a>,
+        platform_fee_ata: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        associated_token_account_program: &AccountInfo<'a>,
+        fund_name: String
+    ) -> ProgramResult {
+        if !payer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        check_token_program(token_program)?;
+
+        if *system_program.key != SYSTEM_PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        if *associated_token_account_program.key != ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]], 
+            program_id
+        )?;
+
+        if pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.fund_mint != *mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.manager_fee_recipient() != *manager_fee_recipient.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.platform_fee_recipient() != *platform_fee_recipient.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        fund_account_data.accrue_management_fee(mint_account, current_timestamp)?;
+        fund_account_data.crystallise_performance_fee(mint_account, current_timestamp)?;
+
+        let manager_fee_shares = fund_account_data.accrued_management_fee_shares
+            .checked_add(fund_account_data.accrued_performance_fee_shares)
+            .and_then(|shares| shares.checked_add(fund_account_data.accrued_manager_fee_shares))
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+        let platform_fee_shares = fund_account_data.accrued_platform_fee_shares;
+
+        fund_account_data.accrued_management_fee_shares = 0;
+        fund_account_data.accrued_performance_fee_shares = 0;
+        fund_account_data.accrued_manager_fee_shares = 0;
+        fund_account_data.accrued_platform_fee_shares = 0;
+
+        fund_account_data.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
+
+        let fund_account_seeds: &[&[u8]] = &[
+            b"fund_account",
+            fund_name.as_bytes(),
+            &[fund_account_data.bump_seed]
+        ];
+
+        for (recipient, recipient_ata, fee_shares) in [
+            (manager_fee_recipient, manager_fee_ata, manager_fee_shares),
+            (platform_fee_recipient, platform_fee_ata, platform_fee_shares),
+        ] {
+            if fee_shares == 0 {
+                continue;
+            }
+
+            let recipient_ata_address = get_associated_token_address_with_program_id(
+                recipient.key, 
+                mint_account.key, 
+                token_program.key
+            );
+
+            if recipient_ata_address != *recipient_ata.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if recipient_ata.lamports() == 0 {
+                let create_ata_ix = create_associated_token_account(
+                    payer.key, 
+                    recipient.key, 
+                    mint_account.key, 
+                    token_program.key
+                );
+
+                invoke(
+                    &create_ata_ix, 
+                    &[
+                        mint_account.clone(),
+                        recipient_ata.clone(),
+                        payer.clone(),
+                        recipient.clone(),
+                        token_program.clone(),
+                        system_program.clone(),
+                        associated_token_account_program.clone(),
+                    ]
+                )?;
+            }
+
+            let mint_fee_shares_ix = mint_to(
+                token_program.key, 
+                mint_account.key, 
+                recipient_ata.key, 
+                fund_account.key, 
+                &[fund_account.key], 
+                fee_shares
+            )?;
+
+            invoke_signed(
+                &mint_fee_shares_ix, 
+                &[
+                    token_program.clone(),
+                    mint_account.clone(),
+                    recipient_ata.clone(),
+                    fund_account.clone()
+                ], 
+                &[fund_account_seeds]
+            )?;
+
+            msg!("minted {} fee shares to {}", fee_shares, recipient.key);
+        }
+
+        Ok(())
+    }
+
+    pub fn check_share_value_freshness(&self, current_timestamp: i64) -> ProgramResult {
+        let max_nav_age_seconds = self.config.max_nav_age_seconds;
+
+        if max_nav_age_seconds > 0 
+            && current_timestamp.saturating_sub(self.share_value_update) > max_nav_age_seconds {
+            return Err(CustomError::StaleShareValue.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn manager_fee_recipient(&self) -> Pubkey {
+        if self.config.manager_fee_recipient == Pubkey::default() {
+            return self.manager_master_pubkey;
+        }
+
+        self.config.manager_fee_recipient
+    }
+
+    pub fn check_settlement_token_program(&self, settlement_token_program: &AccountInfo) -> ProgramResult {
+        if self.settlement_token_program != Some(*settlement_token_program.key) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    pub fn platform_fee_recipient(&self) -> Pubkey {
+        if self.config.platform_fee_recipient == Pubkey::default() {
+            return self.punto_xero_master_pubkey;
+        }
+
+        self.config.platform_fee_recipient
+    }
+
+    pub fn accrue_transaction_fee(&mut self, fee_shares: u64) -> ProgramResult {
+        let platform_fee_shares = basis_points_of(
+            fee_shares, 
+            self.config.platform_fee_share_bps
+        )?;
+
+        self.accrued_platform_fee_shares = self.accrued_platform_fee_shares
+            .checked_add(platform_fee_shares)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+        let manager_fee_shares = fee_shares.checked_sub(platform_fee_shares)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+        self.accrued_manager_fee_shares = self.accrued_manager_fee_shares
+            .checked_add(manager_fee_shares)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+        Ok(())
+    }
+}
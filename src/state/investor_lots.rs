@@ -0,0 +1,190 @@
+use solana_program::{
+    pubkey::Pubkey,
+    program_pack::{IsInitialized, Sealed},
+    entrypoint::ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    borsh1::try_from_slice_unchecked,
+    program::invoke_signed,
+    system_instruction::create_account,
+    sysvar::Sysvar,
+    rent::Rent,
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::errors::CustomError;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct SubscriptionLot {
+    pub timestamp: i64,
+    pub shares: u64,
+}
+
+// Shares an investor subscribed to a fund with lock-up, oldest lot first.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct InvestorLots {
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+    pub fund_account: Pubkey,
+    pub investor: Pubkey,
+    pub lots: Vec<SubscriptionLot>,
+}
+
+impl Sealed for InvestorLots {}
+
+impl IsInitialized for InvestorLots {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl InvestorLots {
+
+    pub const MAX_LOTS: usize = 16;
+
+    const LEN: usize = 1      // for is_initialized
+        + 1                   // for bump_seed
+        + 32                  // for fund_account
+        + 32                  // for investor
+        + 4 + 16 * Self::MAX_LOTS;
+
+    pub fn address(program_id: &Pubkey, investor: &Pubkey, fund_name: &str) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"investor_lots", fund_name.as_bytes(), &investor.to_bytes()],
+            program_id
+        )
+    }
+
+    pub fn load_or_create<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        investor_lots_account: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        investor: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        fund_name: &str,
+    ) -> Result<Self, ProgramError> {
+
+        let (pda, bump_seed) = Self::address(program_id, investor.key, fund_name);
+
+        if pda != *investor_lots_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if investor_lots_account.lamports() == 0 {
+            let rent = Rent::get()?.minimum_balance(Self::LEN);
+
+            invoke_signed(
+                &create_account(
+                    punto_xero.key,
+                    investor_lots_account.key,
+                    rent,
+                    Self::LEN as u64,
+                    program_id
+                ),
+                &[
+                    punto_xero.clone(),
+                    investor_lots_account.clone(),
+                    system_program.clone()
+                ],
+                &[
+                    &[
+                        b"investor_lots",
+                        fund_name.as_bytes(),
+                        &investor.key.to_bytes(),
+                        &[bump_seed]
+                    ]
+                ]
+            )?;
+
+            return Ok(InvestorLots {
+                is_initialized: true,
+                bump_seed,
+                fund_account: *fund_account.key,
+                investor: *investor.key,
+                lots: Vec::new(),
+            });
+        }
+
+        Self::load(program_id, investor_lots_account, investor.key, fund_name)
+    }
+
+    pub fn load(
+        program_id: &Pubkey,
+        investor_lots_account: &AccountInfo,
+        investor: &Pubkey,
+        fund_name: &str,
+    ) -> Result<Self, ProgramError> {
+
+        if investor_lots_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let account_data = try_from_slice_unchecked::<InvestorLots>(
+            &investor_lots_account.data.borrow()[..]
+        )?;
+
+        let pda = Pubkey::create_program_address(
+            &[
+                b"investor_lots",
+                fund_name.as_bytes(),
+                &investor.to_bytes(),
+                &[account_data.bump_seed]
+            ],
+            program_id
+        )?;
+
+        if pda != *investor_lots_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(account_data)
+    }
+
+    pub fn add_lot(&mut self, lot: SubscriptionLot) -> ProgramResult {
+        // once full, fold into the newest lot so the shares stay locked at
+        // least as long as they should
+        if self.lots.len() >= Self::MAX_LOTS {
+            let newest = self.lots.last_mut()
+                .ok_or(ProgramError::InvalidAccountData)?;
+
+            newest.timestamp = newest.timestamp.max(lot.timestamp);
+            newest.shares = newest.shares.checked_add(lot.shares)
+                .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+            return Ok(());
+        }
+
+        self.lots.push(lot);
+
+        Ok(())
+    }
+
+    pub fn release_expired(&mut self, lock_up_seconds: i64, current_timestamp: i64) {
+        self.lots.retain(|lot| lot.timestamp.saturating_add(lock_up_seconds) > current_timestamp);
+    }
+
+    pub fn locked_shares(&self) -> u64 {
+        self.lots.iter().map(|lot| lot.shares).sum()
+    }
+
+    pub fn consume_locked(&mut self, mut shares: u64) {
+        while shares > 0 && !self.lots.is_empty() {
+            let taken = self.lots[0].shares.min(shares);
+
+            self.lots[0].shares -= taken;
+            shares -= taken;
+
+            if self.lots[0].shares == 0 {
+                self.lots.remove(0);
+            }
+        }
+    }
+
+    pub fn save(&self, investor_lots_account: &AccountInfo) -> ProgramResult {
+        self.serialize(&mut &mut investor_lots_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+}
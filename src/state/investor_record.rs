@@ -0,0 +1,391 @@
+use solana_program::{
+    pubkey::Pubkey,
+    program_pack::{IsInitialized, Sealed},
+    entrypoint::ProgramResult,
+    account_info::AccountInfo,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+    program_error::ProgramError,
+    borsh1::try_from_slice_unchecked,
+    program::invoke_signed,
+    system_instruction::create_account,
+    sysvar::Sysvar,
+    clock::Clock,
+    rent::Rent,
+    msg,
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::instruction::{freeze_account, thaw_account};
+
+use super::{fund_account::FundAccount, share_token::check_token_program};
+
+use crate::errors::CustomError;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum KycStatus {
+    #[default]
+    Unknown,
+    Approved,
+    Suspended,
+    Revoked,
+}
+
+// Compliance record of one investor in one fund.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct InvestorRecord {
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+    pub fund_account: Pubkey,
+    pub investor: Pubkey,
+    pub status: KycStatus,
+    pub expiry_timestamp: i64,
+    pub jurisdiction: [u8; 2],
+    pub updated_timestamp: i64,
+}
+
+impl Sealed for InvestorRecord {}
+
+impl IsInitialized for InvestorRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl InvestorRecord {
+
+    const LEN: usize = 1      // for is_initialized
+        + 1                   // for bump_seed
+        + 32                  // for fund_account
+        + 32                  // for investor
+        + 1                   // for status
+        + 8                   // for expiry_timestamp
+        + 2                   // for jurisdiction
+        + 8;                  // for updated_timestamp
+
+    pub fn address(program_id: &Pubkey, fund_account: &Pubkey, investor: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"investor_record", &fund_account.to_bytes(), &investor.to_bytes()],
+            program_id
+        )
+    }
+
+    // the investor record travels as the last account of an instruction
+    pub fn split_trailing<'b, 'a>(
+        program_id: &Pubkey,
+        fund_account: &Pubkey,
+        investor: &Pubkey,
+        accounts: &'b [AccountInfo<'a>],
+    ) -> (Option<&'b AccountInfo<'a>>, &'b [AccountInfo<'a>]) {
+        let (investor_record_address, _bump_seed) = Self::address(program_id, fund_account, investor);
+
+        match accounts.split_last() {
+            Some((last, rest)) if *last.key == investor_record_address => (Some(last), rest),
+            _ => (None, accounts)
+        }
+    }
+
+    pub fn add_investor<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        compliance_officer: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        investor_record_account: &AccountInfo<'a>,
+        investor: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        expiry_timestamp: i64,
+        jurisdiction: [u8; 2],
+        fund_name: String,
+    ) -> ProgramResult {
+
+        if *system_program.key != SYSTEM_PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        if !punto_xero.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        Self::check_compliance_officer(program_id, compliance_officer, fund_account, &fund_name)?;
+
+        let (investor_record_pda, bump_seed) = Self::address(program_id, fund_account.key, investor.key);
+
+        if investor_record_pda != *investor_record_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if investor_record_account.lamports() == 0 {
+            let rent = Rent::get()?.minimum_balance(Self::LEN);
+
+            invoke_signed(
+                &create_account(
+                    punto_xero.key,
+                    investor_record_account.key,
+                    rent,
+                    Self::LEN as u64,
+                    program_id
+                ),
+                &[
+                    punto_xero.clone(),
+                    investor_record_account.clone(),
+                    system_program.clone()
+                ],
+                &[
+                    &[
+                        b"investor_record",
+                        &fund_account.key.to_bytes(),
+                        &investor.key.to_bytes(),
+                        &[bump_seed]
+                    ]
+                ]
+            )?;
+        } else {
+            if investor_record_account.owner != program_id {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            let existing_record = try_from_slice_unchecked::<InvestorRecord>(
+                &investor_record_account.data.borrow()[..]
+            )?;
+
+            if existing_record.status == KycStatus::Revoked {
+                return Err(CustomError::InvestorRevoked.into());
+            }
+        }
+
+        let investor_record = InvestorRecord {
+            is_initialized: true,
+            bump_seed,
+            fund_account: *fund_account.key,
+            investor: *investor.key,
+            status: KycStatus::Approved,
+            expiry_timestamp,
+            jurisdiction,
+            updated_timestamp: Clock::get()?.unix_timestamp,
+        };
+
+        investor_record.serialize(&mut &mut investor_record_account.data.borrow_mut()[..])?;
+
+        msg!("investor {} approved until {}", investor.key, expiry_timestamp);
+
+        Ok(())
+    }
+
+    pub fn set_investor_status(
+        program_id: &Pubkey,
+        compliance_officer: &AccountInfo,
+        fund_account: &AccountInfo,
+        investor_record_account: &AccountInfo,
+        status: KycStatus,
+        fund_name: String,
+    ) -> ProgramResult {
+
+        Self::check_compliance_officer(program_id, compliance_officer, fund_account, &fund_name)?;
+
+        if investor_record_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut investor_record = try_from_slice_unchecked::<InvestorRecord>(
+            &investor_record_account.data.borrow()[..]
+        )?;
+
+        let investor_record_pda = Pubkey::create_program_address(
+            &[
+                b"investor_record",
+                &fund_account.key.to_bytes(),
+                &investor_record.investor.to_bytes(),
+                &[investor_record.bump_seed]
+            ],
+            program_id
+        )?;
+
+        if investor_record_pda != *investor_record_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        investor_record.status = status;
+        investor_record.updated_timestamp = Clock::get()?.unix_timestamp;
+
+        investor_record.serialize(&mut &mut investor_record_account.data.borrow_mut()[..])?;
+
+        msg!("investor {} is now {:?}", investor_record.investor, status);
+
+        Ok(())
+    }
+
+    // freezes or thaws the investor's share account, the fund account being
+    // the freeze authority of the fund mint
+    pub fn set_account_frozen<'a>(
+        program_id: &Pubkey,
+        compliance_officer: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        mint_account: &AccountInfo<'a>,
+        investor: &AccountInfo<'a>,
+        investor_ata: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        frozen: bool,
+        fund_name: String,
+    ) -> ProgramResult {
+
+        check_token_program(token_program)?;
+
+        Self::check_compliance_officer(program_id, compliance_officer, fund_account, &fund_name)?;
+
+        let fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        if fund_account_data.fund_mint != *mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let investor_ata_address = get_associated_token_address_with_program_id(
+            investor.key, 
+            mint_account.key, 
+            token_program.key
+        );
+
+        if investor_ata_address != *investor_ata.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let freeze_ix = if frozen {
+            freeze_account(
+                token_program.key, 
+                investor_ata.key, 
+                mint_account.key, 
+                fund_account.key, 
+                &[fund_account.key]
+            )?
+        } else {
+            thaw_account(
+                token_program.key, 
+                investor_ata.key, 
+                mint_account.key, 
+                fund_account.key, 
+                &[fund_account.key]
+            )?
+        };
+
+        invoke_signed(
+            &freeze_ix, 
+            &[
+                token_program.clone(),
+                investor_ata.clone(),
+                mint_account.clone(),
+                fund_account.clone()
+            ], 
+            &[
+                &[
+                    b"fund_account",
+                    fund_name.as_bytes(),
+                    &[fund_account_data.bump_seed]
+                ]
+            ]
+        )?;
+
+        msg!("investor {} frozen: {}", investor.key, frozen);
+
+        Ok(())
+    }
+
+    pub fn check_investor(
+        program_id: &Pubkey,
+        fund_account: &Pubkey,
+        fund_account_data: &FundAccount,
+        investor_record_account: Option<&AccountInfo>,
+        investor: &Pubkey,
+        current_timestamp: i64,
+    ) -> ProgramResult {
+
+        if !fund_account_data.config.kyc_required {
+            return Ok(());
+        }
+
+        let Some(investor_record_account) = investor_record_account else {
+            return Err(CustomError::InvestorNotApproved.into());
+        };
+
+        Self::check_approved(program_id, fund_account, investor_record_account, investor, current_timestamp)
+    }
+
+    pub fn check_approved(
+        program_id: &Pubkey,
+        fund_account: &Pubkey,
+        investor_record_account: &AccountInfo,
+        investor: &Pubkey,
+        current_timestamp: i64,
+    ) -> ProgramResult {
+
+        if investor_record_account.owner != program_id {
+            return Err(CustomError::InvestorNotApproved.into());
+        }
+
+        let investor_record = try_from_slice_unchecked::<InvestorRecord>(
+            &investor_record_account.data.borrow()[..]
+        )?;
+
+        let investor_record_pda = Pubkey::create_program_address(
+            &[
+                b"investor_record",
+                &fund_account.to_bytes(),
+                &investor.to_bytes(),
+                &[investor_record.bump_seed]
+            ],
+            program_id
+        )?;
+
+        if investor_record_pda != *investor_record_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if investor_record.status != KycStatus::Approved {
+            return Err(CustomError::InvestorNotApproved.into());
+        }
+
+        if investor_record.expiry_timestamp <= current_timestamp {
+            return Err(CustomError::KycExpired.into());
+        }
+
+        Ok(())
+    }
+
+    fn check_compliance_officer(
+        program_id: &Pubkey,
+        compliance_officer: &AccountInfo,
+        fund_account: &AccountInfo,
+        fund_name: &str,
+    ) -> ProgramResult {
+
+        if !compliance_officer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let fund_account_pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]],
+            program_id
+        )?;
+
+        if fund_account_pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let compliance_officer_key = fund_account_data.config.compliance_officer;
+
+        if compliance_officer_key == Pubkey::default() || compliance_officer_key != *compliance_officer.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        Ok(())
+    }
+}
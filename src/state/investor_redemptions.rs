@@ -0,0 +1,176 @@
+use solana_program::{
+    pubkey::Pubkey,
+    program_pack::{IsInitialized, Sealed},
+    entrypoint::ProgramResult,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    borsh1::try_from_slice_unchecked,
+    program::invoke_signed,
+    system_instruction::create_account,
+    sysvar::Sysvar,
+    rent::Rent,
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::errors::CustomError;
+
+// Tracks the redemption requests of one investor in one fund. Open requests
+// can be enumerated off-chain by deriving a ShareRedemption PDA for every id
+// in `open_request_ids`.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct InvestorRedemptions {
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+    pub fund_account: Pubkey,
+    pub investor: Pubkey,
+    pub next_request_id: u64,
+    pub open_request_ids: Vec<u64>,
+}
+
+impl Sealed for InvestorRedemptions {}
+
+impl IsInitialized for InvestorRedemptions {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl InvestorRedemptions {
+
+    pub const MAX_OPEN_REQUESTS: usize = 12;
+
+    const LEN: usize = 1      // for is_initialized
+        + 1                   // for bump_seed
+        + 32                  // for fund_account
+        + 32                  // for investor
+        + 8                   // for next_request_id
+        + 4 + 8 * Self::MAX_OPEN_REQUESTS;
+
+    pub fn load_or_create<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        investor_redemptions_account: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        investor: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        fund_name: &str,
+    ) -> Result<Self, ProgramError> {
+
+        let (pda, bump_seed) = Pubkey::find_program_address(
+            &[b"investor_redemptions", fund_name.as_bytes(), &investor.key.to_bytes()],
+            program_id
+        );
+
+        if pda != *investor_redemptions_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if investor_redemptions_account.lamports() == 0 {
+            let rent = Rent::get()?.minimum_balance(Self::LEN);
+
+            invoke_signed(
+                &create_account(
+                    punto_xero.key,
+                    investor_redemptions_account.key,
+                    rent,
+                    Self::LEN as u64,
+                    program_id
+                ),
+                &[
+                    punto_xero.clone(),
+                    investor_redemptions_account.clone(),
+                    system_program.clone()
+                ],
+                &[
+                    &[
+                        b"investor_redemptions",
+                        fund_name.as_bytes(),
+                        &investor.key.to_bytes(),
+                        &[bump_seed]
+                    ]
+                ]
+            )?;
+
+            return Ok(InvestorRedemptions {
+                is_initialized: true,
+                bump_seed,
+                fund_account: *fund_account.key,
+                investor: *investor.key,
+                next_request_id: 0,
+                open_request_ids: Vec::new(),
+            });
+        }
+
+        if investor_redemptions_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        try_from_slice_unchecked::<InvestorRedemptions>(
+            &investor_redemptions_account.data.borrow()[..]
+        ).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn load(
+        program_id: &Pubkey,
+        investor_redemptions_account: &AccountInfo,
+        investor: &Pubkey,
+        fund_name: &str,
+    ) -> Result<Self, ProgramError> {
+
+        if investor_redemptions_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let account_data = try_from_slice_unchecked::<InvestorRedemptions>(
+            &investor_redemptions_account.data.borrow()[..]
+        )?;
+
+        let pda = Pubkey::create_program_address(
+            &[
+                b"investor_redemptions",
+                fund_name.as_bytes(),
+                &investor.to_bytes(),
+                &[account_data.bump_seed]
+            ],
+            program_id
+        )?;
+
+        if pda != *investor_redemptions_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(account_data)
+    }
+
+    pub fn open_request(&mut self) -> Result<u64, ProgramError> {
+        if self.open_request_ids.len() >= Self::MAX_OPEN_REQUESTS {
+            return Err(CustomError::TooManyOpenRedemptions.into());
+        }
+
+        let request_id = self.next_request_id;
+
+        self.next_request_id = self.next_request_id.checked_add(1)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+        self.open_request_ids.push(request_id);
+
+        Ok(request_id)
+    }
+
+    pub fn close_request(&mut self, request_id: u64) -> ProgramResult {
+        let position = self.open_request_ids
+            .iter()
+            .position(|id| *id == request_id)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        self.open_request_ids.remove(position);
+
+        Ok(())
+    }
+
+    pub fn save(&self, investor_redemptions_account: &AccountInfo) -> ProgramResult {
+        self.serialize(&mut &mut investor_redemptions_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+}
@@ -1,2 +1,12 @@
 pub mod fund_account;
-pub mod share_redemption;
\ No newline at end of file
+pub mod share_redemption;
+pub mod investor_redemptions;
+pub mod nav_history;
+pub mod price_feed;
+pub mod pending_nav;
+pub mod subscription_request;
+pub mod dealing_calendar;
+pub mod investor_lots;
+pub mod investor_record;
+pub mod share_token;
+pub mod transfer_hook;
@@ -0,0 +1,243 @@
+use solana_program::{
+    pubkey::Pubkey,
+    program_pack::{IsInitialized, Sealed},
+    entrypoint::ProgramResult,
+    account_info::AccountInfo,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+    program_error::ProgramError,
+    borsh1::try_from_slice_unchecked,
+    program::invoke_signed,
+    system_instruction::create_account,
+    sysvar::Sysvar,
+    rent::Rent,
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use super::fund_account::FundAccount;
+
+use crate::errors::CustomError;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct NavEntry {
+    pub timestamp: i64,
+    pub share_value: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct NavHistory {
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+    pub fund_account: Pubkey,
+    pub head: u32,
+    pub entries: Vec<NavEntry>,
+}
+
+impl Sealed for NavHistory {}
+
+impl IsInitialized for NavHistory {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl NavHistory {
+
+    pub const CAPACITY: usize = 64;
+
+    const LEN: usize = 1      // for is_initialized
+        + 1                   // for bump_seed
+        + 32                  // for fund_account
+        + 4                   // for head
+        + 4 + 16 * Self::CAPACITY;
+
+    pub fn initialize_nav_history<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        manager: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        nav_history_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        fund_name: String,
+    ) -> ProgramResult {
+
+        if *system_program.key != SYSTEM_PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        if !punto_xero.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !manager.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let fund_account_pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]],
+            program_id
+        )?;
+
+        if fund_account_pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.punto_xero_master_pubkey != *punto_xero.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account_data.manager_master_pubkey != *manager.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account_data.nav_history.is_some() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let (nav_history_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"nav_history", &fund_account.key.to_bytes()],
+            program_id
+        );
+
+        if nav_history_pda != *nav_history_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let rent = Rent::get()?.minimum_balance(Self::LEN);
+
+        invoke_signed(
+            &create_account(
+                punto_xero.key,
+                nav_history_account.key,
+                rent,
+                Self::LEN as u64,
+                program_id
+            ),
+            &[
+                punto_xero.clone(),
+                nav_history_account.clone(),
+                system_program.clone()
+            ],
+            &[
+                &[
+                    b"nav_history",
+                    &fund_account.key.to_bytes(),
+                    &[bump_seed]
+                ]
+            ]
+        )?;
+
+        let mut nav_history = NavHistory {
+            is_initialized: true,
+            bump_seed,
+            fund_account: *fund_account.key,
+            head: 0,
+            entries: Vec::with_capacity(Self::CAPACITY),
+        };
+
+        nav_history.push(NavEntry {
+            timestamp: fund_account_data.share_value_update,
+            share_value: fund_account_data.share_value,
+        });
+
+        nav_history.serialize(&mut &mut nav_history_account.data.borrow_mut()[..])?;
+
+        fund_account_data.nav_history = Some(*nav_history_account.key);
+
+        fund_account_data.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    pub fn load(
+        program_id: &Pubkey,
+        fund_account_data: &FundAccount,
+        nav_history_account: &AccountInfo,
+    ) -> Result<Self, ProgramError> {
+        let Some(nav_history_key) = fund_account_data.nav_history else {
+            return Err(CustomError::DealingShareValueUnavailable.into());
+        };
+
+        if nav_history_key != *nav_history_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if nav_history_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        try_from_slice_unchecked::<NavHistory>(&nav_history_account.data.borrow()[..])
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn record(
+        program_id: &Pubkey,
+        fund_account_data: &FundAccount,
+        nav_history_account: Option<&AccountInfo>,
+    ) -> ProgramResult {
+        if fund_account_data.nav_history.is_none() {
+            return Ok(());
+        }
+
+        let Some(nav_history_account) = nav_history_account else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        let mut nav_history = Self::load(program_id, fund_account_data, nav_history_account)?;
+
+        nav_history.push(NavEntry {
+            timestamp: fund_account_data.share_value_update,
+            share_value: fund_account_data.share_value,
+        });
+
+        nav_history.serialize(&mut &mut nav_history_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    pub fn push(&mut self, entry: NavEntry) {
+        if self.entries.len() < Self::CAPACITY {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.head as usize] = entry;
+        }
+
+        self.head = ((self.head as usize + 1) % Self::CAPACITY) as u32;
+    }
+
+    // first share value struck strictly after `timestamp`, which is the
+    // dealing price for a forward priced request made at that time
+    pub fn first_after(&self, timestamp: i64) -> Result<NavEntry, ProgramError> {
+        let oldest_first = self.latest(self.entries.len()).into_iter().rev();
+
+        for (position, entry) in oldest_first.enumerate() {
+            if entry.timestamp > timestamp {
+                // once the buffer has wrapped, the oldest entry may not be the
+                // first one after the request
+                if position == 0 && self.entries.len() == Self::CAPACITY {
+                    return Err(CustomError::DealingShareValueUnavailable.into());
+                }
+
+                return Ok(entry);
+            }
+        }
+
+        Err(CustomError::AwaitingNextShareValue.into())
+    }
+
+    pub fn latest(&self, count: usize) -> Vec<NavEntry> {
+        let len = self.entries.len();
+
+        (1..=count.min(len))
+            .map(|offset| self.entries[(self.head as usize + len - offset) % len])
+            .collect()
+    }
+}
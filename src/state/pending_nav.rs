@@ -0,0 +1,261 @@
+use solana_program::{
+    pubkey::Pubkey,
+    program_pack::{IsInitialized, Sealed},
+    entrypoint::ProgramResult,
+    account_info::AccountInfo,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+    program_error::ProgramError,
+    borsh1::try_from_slice_unchecked,
+    program::invoke_signed,
+    system_instruction::create_account,
+    sysvar::Sysvar,
+    clock::Clock,
+    rent::Rent,
+    msg,
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use super::fund_account::{FundAccount, FundConfig};
+
+use crate::errors::CustomError;
+
+// Share value proposed by the manager that only becomes effective once
+// `nav_attestation_threshold` of the fund's attesters approve it.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct PendingNav {
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+    pub fund_account: Pubkey,
+    pub is_open: bool,
+    pub proposed_share_value: u64,
+    pub proposed_timestamp: i64,
+    pub approvals: Vec<Pubkey>,
+}
+
+impl Sealed for PendingNav {}
+
+impl IsInitialized for PendingNav {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl PendingNav {
+
+    const LEN: usize = 1      // for is_initialized
+        + 1                   // for bump_seed
+        + 32                  // for fund_account
+        + 1                   // for is_open
+        + 8                   // for proposed_share_value
+        + 8                   // for proposed_timestamp
+        + 4 + 32 * FundConfig::MAX_NAV_ATTESTERS;
+
+    pub fn propose_share_value<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        manager: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        pending_nav_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        proposed_share_value: u64,
+        fund_name: String,
+    ) -> ProgramResult {
+
+        if *system_program.key != SYSTEM_PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        if !punto_xero.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !manager.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if proposed_share_value == 0 {
+            return Err(CustomError::InvalidShareValue.into());
+        }
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let fund_account_pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]],
+            program_id
+        )?;
+
+        if fund_account_pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.punto_xero_master_pubkey != *punto_xero.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account_data.manager_master_pubkey != *manager.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account_data.config.nav_attestation_threshold == 0 {
+            return Err(CustomError::NavAttestationDisabled.into());
+        }
+
+        let (pending_nav_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"pending_nav", &fund_account.key.to_bytes()],
+            program_id
+        );
+
+        if pending_nav_pda != *pending_nav_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if pending_nav_account.lamports() == 0 {
+            let rent = Rent::get()?.minimum_balance(Self::LEN);
+
+            invoke_signed(
+                &create_account(
+                    punto_xero.key,
+                    pending_nav_account.key,
+                    rent,
+                    Self::LEN as u64,
+                    program_id
+                ),
+                &[
+                    punto_xero.clone(),
+                    pending_nav_account.clone(),
+                    system_program.clone()
+                ],
+                &[
+                    &[
+                        b"pending_nav",
+                        &fund_account.key.to_bytes(),
+                        &[bump_seed]
+                    ]
+                ]
+            )?;
+        } else if pending_nav_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        // a new proposal replaces any earlier one along with its approvals
+        let pending_nav = PendingNav {
+            is_initialized: true,
+            bump_seed,
+            fund_account: *fund_account.key,
+            is_open: true,
+            proposed_share_value,
+            proposed_timestamp: Clock::get()?.unix_timestamp,
+            approvals: Vec::new(),
+        };
+
+        pending_nav.serialize(&mut &mut pending_nav_account.data.borrow_mut()[..])?;
+
+        msg!("share value proposed: {}", proposed_share_value);
+
+        Ok(())
+    }
+
+    pub fn approve_share_value(
+        program_id: &Pubkey,
+        attester: &AccountInfo,
+        fund_account: &AccountInfo,
+        mint_account: &AccountInfo,
+        pending_nav_account: &AccountInfo,
+        nav_history_account: Option<&AccountInfo>,
+        fund_name: String,
+    ) -> ProgramResult {
+
+        if !attester.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let fund_account_pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]],
+            program_id
+        )?;
+
+        if fund_account_pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.fund_mint != *mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !fund_account_data.config.nav_attesters.contains(attester.key) {
+            return Err(CustomError::NotANavAttester.into());
+        }
+
+        if pending_nav_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut pending_nav = try_from_slice_unchecked::<PendingNav>(
+            &pending_nav_account.data.borrow()[..]
+        )?;
+
+        let pending_nav_pda = Pubkey::create_program_address(
+            &[b"pending_nav", &fund_account.key.to_bytes(), &[pending_nav.bump_seed]],
+            program_id
+        )?;
+
+        if pending_nav_pda != *pending_nav_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !pending_nav.is_open {
+            return Err(CustomError::NoPendingShareValue.into());
+        }
+
+        let window_seconds = fund_account_data.config.nav_attestation_window_seconds;
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        if window_seconds > 0 
+            && current_timestamp.saturating_sub(pending_nav.proposed_timestamp) > window_seconds {
+            return Err(CustomError::NavProposalExpired.into());
+        }
+
+        if pending_nav.approvals.contains(attester.key) {
+            return Err(CustomError::DuplicateNavAttestation.into());
+        }
+
+        pending_nav.approvals.push(*attester.key);
+
+        if pending_nav.approvals.len() >= fund_account_data.config.nav_attestation_threshold as usize {
+            fund_account_data.check_share_value_change(pending_nav.proposed_share_value)?;
+
+            fund_account_data.set_share_value(
+                program_id, 
+                mint_account, 
+                nav_history_account, 
+                pending_nav.proposed_share_value,
+                current_timestamp
+            )?;
+
+            fund_account_data.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
+
+            pending_nav.is_open = false;
+
+            msg!("share value attested: {}", pending_nav.proposed_share_value);
+        }
+
+        pending_nav.serialize(&mut &mut pending_nav_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use solana_program::{
+    pubkey::Pubkey,
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    borsh1::try_from_slice_unchecked,
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    errors::CustomError,
+    utils::BASIS_POINTS
+};
+
+// Generic price-feed layout read from an oracle account owned by the oracle
+// program configured on the fund. `price` and `confidence` use the same
+// fixed-point scale as `FundAccount::share_value`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default)]
+pub struct PriceFeed {
+    pub price: u64,
+    pub confidence: u64,
+    pub publish_timestamp: i64,
+}
+
+impl PriceFeed {
+
+    pub fn load(
+        oracle_account: &AccountInfo,
+        oracle: &Pubkey,
+        oracle_program: &Pubkey
+    ) -> Result<Self, ProgramError> {
+
+        if *oracle == Pubkey::default() {
+            return Err(CustomError::OracleNotConfigured.into());
+        }
+
+        if *oracle_account.key != *oracle {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if *oracle_account.owner != *oracle_program {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        try_from_slice_unchecked::<PriceFeed>(&oracle_account.data.borrow()[..])
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    pub fn check(
+        &self,
+        max_confidence_bps: u16,
+        max_age_seconds: i64,
+        current_timestamp: i64
+    ) -> Result<u64, ProgramError> {
+
+        if self.price == 0 {
+            return Err(CustomError::InvalidShareValue.into());
+        }
+
+        if self.publish_timestamp > current_timestamp {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if max_age_seconds > 0 
+            && current_timestamp.saturating_sub(self.publish_timestamp) > max_age_seconds {
+            return Err(CustomError::StaleOraclePrice.into());
+        }
+
+        if max_confidence_bps > 0 
+            && self.confidence as u128 * BASIS_POINTS as u128 
+                > self.price as u128 * max_confidence_bps as u128 {
+            return Err(CustomError::OracleConfidenceTooWide.into());
+        }
+
+        Ok(self.price)
+    }
+}
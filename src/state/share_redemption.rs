@@ -6,24 +6,31 @@ use solana_program::{
     system_program::ID as SYSTEM_PROGRAM_ID,
     program_error::ProgramError,
     borsh1::try_from_slice_unchecked, 
-    program::{invoke_signed, invoke},
+    program::invoke_signed,
     system_instruction::create_account, 
     sysvar::Sysvar,
     rent::Rent,
     clock::Clock, 
     msg
 };
-use spl_associated_token_account::get_associated_token_address;
-use spl_token::{
-    ID as TOKEN_PROGRAM_ID,
-    instruction::{transfer, burn},
-};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::instruction::burn;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 
-use crate::{errors::CustomError, utils::fixed_point_multiply_checked};
+use crate::{
+    errors::CustomError, 
+    utils::{basis_points_of, fixed_point_multiply_checked}
+};
 
-use super::fund_account::FundAccount;
+use super::{
+    fund_account::{FundAccount, PricingMode}, 
+    investor_lots::InvestorLots, 
+    investor_record::InvestorRecord, 
+    investor_redemptions::InvestorRedemptions, 
+    nav_history::NavHistory, 
+    share_token::{check_token_program, move_shares, transfer, unpack_token_account}
+};
 
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct ShareRedemption {
@@ -31,9 +38,17 @@ pub struct ShareRedemption {
     pub bump_seed: u8,
     pub fund_account: Pubkey,
     pub investor: Pubkey,
+    pub request_id: u64,
     pub shares_amount: u64,
     pub share_value: u64,
     pub created_timestamp: i64,
+    pub processed_shares: u64,
+    pub amount_payed: u64,
+    pub fill_count: u32,
+    pub last_fill_timestamp: i64,
+    pub dealing_timestamp: i64,
+    pub is_overdue: bool,
+    pub escalated_timestamp: i64,
 }
 
 impl Sealed for ShareRedemption {}
@@ -53,7 +68,15 @@ impl ShareRedemption {
         + 32
         + 8
         + 8
-        + 8;
+        + 8
+        + 8
+        + 8      // for processed_shares
+        + 8      // for amount_payed
+        + 4      // for fill_count
+        + 8      // for last_fill_timestamp
+        + 8      // for dealing_timestamp
+        + 1      // for is_overdue
+        + 8;     // for escalated_timestamp
 
     pub fn create_share_redemption<'a>(
         program_id: &Pubkey,
@@ -62,10 +85,13 @@ impl ShareRedemption {
         mint_account: &AccountInfo<'a>,
         fund_vault: &AccountInfo<'a>,
         share_redemption_account: &AccountInfo<'a>,
+        investor_redemptions_account: &AccountInfo<'a>,
         investor: &AccountInfo<'a>,
         investor_ata: &AccountInfo<'a>,
         token_program: &AccountInfo<'a>,
         system_program: &AccountInfo<'a>,
+        investor_lots_account: Option<&AccountInfo<'a>>,
+        investor_record_account: Option<&AccountInfo<'a>>,
         fund_name: String,
         shares_to_redeem: u64,
     ) -> ProgramResult {
@@ -82,11 +108,9 @@ impl ShareRedemption {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        if *token_program.key != TOKEN_PROGRAM_ID {
-            return Err(ProgramError::InvalidAccountData)
-        }
+        check_token_program(token_program)?;
 
-        let fund_account_data = try_from_slice_unchecked::<FundAccount>(
+        let mut fund_account_data = try_from_slice_unchecked::<FundAccount>(
             &fund_account.data.borrow()[..]
         )?;
 
@@ -99,9 +123,10 @@ impl ShareRedemption {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        let fund_vault_pda = get_associated_token_address(
+        let fund_vault_pda = get_associated_token_address_with_program_id(
             &fund_account.key, 
-            &mint_account.key
+            &mint_account.key, 
+            token_program.key
         );
 
         if fund_vault_pda != *fund_vault.key {
@@ -117,8 +142,100 @@ impl ShareRedemption {
             return Err(ProgramError::InvalidAccountData)
         }
 
+        InvestorRecord::check_investor(
+            program_id, 
+            fund_account.key, 
+            &fund_account_data, 
+            investor_record_account, 
+            investor.key, 
+            Clock::get()?.unix_timestamp
+        )?;
+
+        fund_account_data.check_share_value_freshness(Clock::get()?.unix_timestamp)?;
+
+        let dealing_timestamp = fund_account_data.config.dealing_schedule
+            .dealing_timestamp_for(Clock::get()?.unix_timestamp)?;
+
+        let min_redemption_shares = fund_account_data.config.min_redemption_shares;
+
+        // an investor can always exit their whole holding
+        if shares_to_redeem < min_redemption_shares 
+            && shares_to_redeem != unpack_token_account(investor_ata)?.amount {
+            return Err(CustomError::BelowMinimumRedemption.into());
+        }
+
+        fund_account_data.accrue_management_fee(mint_account, Clock::get()?.unix_timestamp)?;
+
+        let mut penalty_shares = 0;
+        let lock_up_seconds = fund_account_data.config.lock_up_seconds;
+
+        if lock_up_seconds > 0 {
+            let Some(investor_lots_account) = investor_lots_account else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            let (investor_lots_pda, _) = InvestorLots::address(program_id, investor.key, &fund_name);
+
+            if investor_lots_pda != *investor_lots_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // no lots account yet means nothing was ever bought under lock-up
+            if investor_lots_account.lamports() > 0 {
+                let mut investor_lots = InvestorLots::load(
+                    program_id, 
+                    investor_lots_account, 
+                    investor.key, 
+                    &fund_name
+                )?;
+
+                investor_lots.release_expired(lock_up_seconds, Clock::get()?.unix_timestamp);
+
+                let balance = unpack_token_account(investor_ata)?.amount;
+                let free_shares = balance.saturating_sub(investor_lots.locked_shares());
+                let locked_shares = shares_to_redeem.saturating_sub(free_shares);
+
+                if locked_shares > 0 {
+                    let early_redemption_fee_bps = fund_account_data.config.early_redemption_fee_bps;
+
+                    if early_redemption_fee_bps == 0 {
+                        return Err(CustomError::SharesLockedUp.into());
+                    }
+
+                    penalty_shares = basis_points_of(locked_shares, early_redemption_fee_bps)?;
+
+                    investor_lots.consume_locked(locked_shares);
+
+                    msg!("early redemption of {} locked shares, penalty {}", locked_shares, penalty_shares);
+                }
+
+                investor_lots.save(investor_lots_account)?;
+            }
+        }
+
+        fund_account_data.accrue_transaction_fee(penalty_shares)?;
+
+        fund_account_data.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
+
+        let mut investor_redemptions = InvestorRedemptions::load_or_create(
+            program_id, 
+            punto_xero, 
+            investor_redemptions_account, 
+            fund_account, 
+            investor, 
+            system_program, 
+            &fund_name
+        )?;
+
+        let request_id = investor_redemptions.open_request()?;
+
         let (share_redemption_pda, share_redemption_bump) = Pubkey::find_program_address(
-            &[b"share_redemption", fund_name.as_bytes(), &investor.key.to_bytes()], 
+            &[
+                b"share_redemption", 
+                fund_name.as_bytes(), 
+                &investor.key.to_bytes(), 
+                &request_id.to_le_bytes()
+            ], 
             program_id
         );
 
@@ -148,6 +265,7 @@ impl ShareRedemption {
                     b"share_redemption",
                     fund_name.as_bytes(),
                     &investor.key.to_bytes(),
+                    &request_id.to_le_bytes(),
                     &[share_redemption_bump]
                 ]
             ]
@@ -165,31 +283,62 @@ impl ShareRedemption {
         share_redemption_account_data.bump_seed = share_redemption_bump;
         share_redemption_account_data.fund_account = *fund_account.key;
         share_redemption_account_data.investor = *investor.key;
-        share_redemption_account_data.shares_amount = shares_to_redeem;
+        share_redemption_account_data.request_id = request_id;
+        share_redemption_account_data.shares_amount = shares_to_redeem.checked_sub(penalty_shares)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
         share_redemption_account_data.share_value = fund_account_data.share_value;
         share_redemption_account_data.created_timestamp = current_timestamp;
+        share_redemption_account_data.dealing_timestamp = dealing_timestamp;
 
         share_redemption_account_data.serialize(&mut &mut share_redemption_account.data.borrow_mut()[..])?;
 
-        let tranfer_ix = transfer(
-            &token_program.key, 
-            &investor_ata.key, 
-            &fund_vault.key, 
-            &investor.key, 
-            &[investor.key], 
-            shares_to_redeem
-        )?;
+        investor_redemptions.save(investor_redemptions_account)?;
+
+        msg!("opened redemption request {}", request_id);
 
-        invoke(
-            &tranfer_ix, 
+        move_shares(
+            token_program, 
+            mint_account, 
+            investor_ata, 
+            fund_vault, 
+            investor, 
+            fund_account, 
             &[
-                token_program.clone(),
-                investor_ata.clone(),
-                fund_vault.clone(),
-                investor.clone()
-            ]
+                b"fund_account",
+                fund_name.as_bytes(),
+                &[fund_account_data.bump_seed]
+            ], 
+            shares_to_redeem
         )?;
 
+        if penalty_shares > 0 {
+            let burn_ix = burn(
+                token_program.key, 
+                fund_vault.key, 
+                mint_account.key, 
+                fund_account.key, 
+                &[fund_account.key], 
+                penalty_shares
+            )?;
+
+            invoke_signed(
+                &burn_ix, 
+                &[
+                    token_program.clone(),
+                    fund_vault.clone(),
+                    mint_account.clone(),
+                    fund_account.clone(),
+                ], 
+                &[
+                    &[
+                        b"fund_account",
+                        fund_name.as_bytes(),
+                        &[fund_account_data.bump_seed]
+                    ]
+                ]
+            )?;
+        }
+
         Ok(())
     }
 
@@ -200,10 +349,14 @@ impl ShareRedemption {
         fund_account: &AccountInfo<'a>,
         mint_account: &AccountInfo<'a>,
         share_redemption_account: &AccountInfo<'a>,
+        investor_redemptions_account: &AccountInfo<'a>,
         fund_vault: &AccountInfo<'a>,
         investor: &AccountInfo<'a>,
         token_program: &AccountInfo<'a>,
         system_program: &AccountInfo<'a>,
+        payout_accounts: Option<(&AccountInfo<'a>, &AccountInfo<'a>, &AccountInfo<'a>)>,
+        nav_history_account: Option<&AccountInfo<'a>>,
+        shares_to_process: u64,
         amount_payed: u64,
         fund_name: String,
     ) -> ProgramResult {
@@ -222,7 +375,7 @@ impl ShareRedemption {
             return Err(ProgramError::IllegalOwner);
         }
 
-        let fund_account_data = try_from_slice_unchecked::<FundAccount>(
+        let mut fund_account_data = try_from_slice_unchecked::<FundAccount>(
             &fund_account.data.borrow()[..]
         )?;
 
@@ -235,14 +388,20 @@ impl ShareRedemption {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        if *system_program.key != SYSTEM_PROGRAM_ID {
-            return Err(ProgramError::InvalidAccountData)
+        if fund_account_data.punto_xero_master_pubkey != *punto_xero.key {
+            return Err(ProgramError::MissingRequiredSignature);
         }
 
-        if *token_program.key != TOKEN_PROGRAM_ID {
+        if fund_account_data.manager_master_pubkey != *manager_master.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if *system_program.key != SYSTEM_PROGRAM_ID {
             return Err(ProgramError::InvalidAccountData)
         }
 
+        check_token_program(token_program)?;
+
         let mint_pda = Pubkey::create_program_address(
             &[b"fund_mint", fund_name.as_bytes(), &[fund_account_data.mint_bump_seed]], 
             program_id
@@ -252,43 +411,160 @@ impl ShareRedemption {
             return Err(ProgramError::InvalidAccountData)
         }
 
-        let fund_vault_pda = get_associated_token_address(
+        let fund_vault_pda = get_associated_token_address_with_program_id(
             &fund_account.key, 
-            &mint_account.key
+            &mint_account.key, 
+            token_program.key
         );
 
         if fund_vault_pda != *fund_vault.key {
             return Err(ProgramError::InvalidAccountData)
         }
 
-        let (share_redemption_pda, _share_redemption_bump) = Pubkey::find_program_address(
-            &[b"share_redemption", fund_name.as_bytes(), &investor.key.to_bytes()], 
+        if share_redemption_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut share_redemption_data 
+            = try_from_slice_unchecked::<ShareRedemption>(&share_redemption_account.data.borrow()[..])?;
+
+        let share_redemption_pda = Pubkey::create_program_address(
+            &[
+                b"share_redemption", 
+                fund_name.as_bytes(), 
+                &investor.key.to_bytes(), 
+                &share_redemption_data.request_id.to_le_bytes(),
+                &[share_redemption_data.bump_seed]
+            ], 
             program_id
-        );
+        )?;
 
         if share_redemption_pda != *share_redemption_account.key {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        let share_redemption_data 
-            = try_from_slice_unchecked::<ShareRedemption>(&mut &mut share_redemption_account.data.borrow_mut()[..])?;
+        if Clock::get()?.unix_timestamp < share_redemption_data.dealing_timestamp {
+            return Err(CustomError::BeforeDealingDate.into());
+        }
 
-        let amount_to_be_payed = fixed_point_multiply_checked(
-            share_redemption_data.share_value,
-            share_redemption_data.shares_amount
-        )?;
+        let notice_seconds = fund_account_data.config.redemption_notice_seconds;
 
-        if amount_to_be_payed != amount_payed {
+        if notice_seconds > 0 {
+            let notice_timestamp = share_redemption_data.created_timestamp
+                .checked_add(notice_seconds)
+                .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+            if Clock::get()?.unix_timestamp < notice_timestamp {
+                return Err(CustomError::NoticePeriodNotElapsed.into());
+            }
+        }
+
+        if shares_to_process == 0 || shares_to_process > share_redemption_data.shares_amount {
             return Err(CustomError::InvalidRedemptionAmount.into())
         }
 
+        let dealing_share_value = match fund_account_data.config.redemption_pricing {
+            PricingMode::Historic => share_redemption_data.share_value,
+            PricingMode::Forward => {
+                let Some(nav_history_account) = nav_history_account else {
+                    return Err(ProgramError::NotEnoughAccountKeys);
+                };
+
+                NavHistory::load(program_id, &fund_account_data, nav_history_account)?
+                    .first_after(share_redemption_data.dealing_timestamp)?
+                    .share_value
+            }
+        };
+
+        let redemption_fee_bps = fund_account_data.config.redemption_fee_bps;
+        let share_decimals = fund_account_data.share_decimals;
+
+        // fee shares and the amount owed for the rest
+        let payout_for = |shares: u64| -> Result<(u64, u64), ProgramError> {
+            let fee_shares = basis_points_of(shares, redemption_fee_bps)?;
+
+            let net_shares = shares.checked_sub(fee_shares)
+                .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+            Ok((fee_shares, fixed_point_multiply_checked(dealing_share_value, net_shares, share_decimals)?))
+        };
+
+        // the manager signs for the shares they asked to process, the gate
+        // may then let fewer through
+        if payout_for(shares_to_process)?.1 != amount_payed {
+            return Err(CustomError::InvalidRedemptionAmount.into())
+        }
+
+        let shares_to_process = fund_account_data.gate_redemption(
+            mint_account, 
+            shares_to_process, 
+            Clock::get()?.unix_timestamp
+        )?;
+
+        let (fee_shares, amount_to_be_payed) = payout_for(shares_to_process)?;
+
+        if let Some(settlement_mint) = fund_account_data.settlement_mint {
+            let Some((cash_vault, investor_payment_account, settlement_token_program)) = payout_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            fund_account_data.check_settlement_token_program(settlement_token_program)?;
+
+            if fund_account_data.cash_vault != Some(*cash_vault.key) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let investor_payment_address = get_associated_token_address_with_program_id(
+                investor.key, 
+                &settlement_mint, 
+                settlement_token_program.key
+            );
+
+            if investor_payment_address != *investor_payment_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let payout_ix = transfer(
+                settlement_token_program.key, 
+                cash_vault.key, 
+                investor_payment_account.key, 
+                fund_account.key, 
+                &[fund_account.key], 
+                amount_to_be_payed
+            )?;
+
+            invoke_signed(
+                &payout_ix, 
+                &[
+                    settlement_token_program.clone(),
+                    cash_vault.clone(),
+                    investor_payment_account.clone(),
+                    fund_account.clone(),
+                ], 
+                &[
+                    &[
+                        b"fund_account",
+                        fund_name.as_bytes(),
+                        &[fund_account_data.bump_seed]
+                    ]
+                ]
+            )?;
+
+            msg!("paid out {} to investor", amount_to_be_payed);
+        }
+
+        fund_account_data.accrue_management_fee(mint_account, Clock::get()?.unix_timestamp)?;
+        fund_account_data.accrue_transaction_fee(fee_shares)?;
+
+        fund_account_data.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
+
         let burn_ix = burn(
             &token_program.key, 
             &fund_vault.key, 
             &mint_account.key, 
             &fund_account.key, 
             &[fund_account.key], 
-            share_redemption_data.shares_amount
+            shares_to_process
         )?;
 
         invoke_signed(
@@ -308,13 +584,283 @@ impl ShareRedemption {
             ]
         )?;
 
+        share_redemption_data.shares_amount -= shares_to_process;
+        share_redemption_data.processed_shares = share_redemption_data.processed_shares
+            .checked_add(shares_to_process)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+        share_redemption_data.amount_payed = share_redemption_data.amount_payed
+            .checked_add(amount_to_be_payed)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+        share_redemption_data.fill_count += 1;
+        share_redemption_data.last_fill_timestamp = Clock::get()?.unix_timestamp;
+
+        msg!(
+            "redemption request {} fill {}: {} shares for {}, {} shares outstanding", 
+            share_redemption_data.request_id,
+            share_redemption_data.fill_count,
+            shares_to_process,
+            amount_to_be_payed,
+            share_redemption_data.shares_amount
+        );
+
+        if share_redemption_data.shares_amount > 0 {
+            share_redemption_data.serialize(&mut &mut share_redemption_account.data.borrow_mut()[..])?;
+
+            return Ok(());
+        }
+
+        let mut investor_redemptions = InvestorRedemptions::load(
+            program_id, 
+            investor_redemptions_account, 
+            investor.key, 
+            &fund_name
+        )?;
+
+        investor_redemptions.close_request(share_redemption_data.request_id)?;
+
+        Self::close_share_redemption_account(share_redemption_account, punto_xero)?;
+
+        investor_redemptions.save(investor_redemptions_account)?;
+
+        Ok(())
+    }
+
+    pub fn cancel_share_redemption<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        mint_account: &AccountInfo<'a>,
+        fund_vault: &AccountInfo<'a>,
+        share_redemption_account: &AccountInfo<'a>,
+        investor_redemptions_account: &AccountInfo<'a>,
+        investor: &AccountInfo<'a>,
+        investor_ata: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        fund_name: String,
+    ) -> ProgramResult {
+
+        if !investor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        check_token_program(token_program)?;
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let fund_account_pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]], 
+            program_id
+        )?;
+
+        if fund_account_pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.punto_xero_master_pubkey != *punto_xero.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.config.cancellation_requires_platform_signature && !punto_xero.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account_data.fund_mint != *mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.fund_vault != *fund_vault.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let investor_ata_address = get_associated_token_address_with_program_id(
+            investor.key, 
+            mint_account.key, 
+            token_program.key
+        );
+
+        if investor_ata_address != *investor_ata.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if share_redemption_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let share_redemption_data = try_from_slice_unchecked::<ShareRedemption>(
+            &share_redemption_account.data.borrow()[..]
+        )?;
+
+        let share_redemption_pda = Pubkey::create_program_address(
+            &[
+                b"share_redemption", 
+                fund_name.as_bytes(), 
+                &investor.key.to_bytes(), 
+                &share_redemption_data.request_id.to_le_bytes(),
+                &[share_redemption_data.bump_seed]
+            ], 
+            program_id
+        )?;
+
+        if share_redemption_pda != *share_redemption_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let cutoff_seconds = fund_account_data.config.cancellation_cutoff_seconds;
+
+        if cutoff_seconds > 0 {
+            let cutoff_timestamp = share_redemption_data.created_timestamp
+                .checked_add(cutoff_seconds)
+                .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+            if Clock::get()?.unix_timestamp > cutoff_timestamp {
+                return Err(CustomError::CancellationWindowClosed.into());
+            }
+        }
+
+        let mut investor_redemptions = InvestorRedemptions::load(
+            program_id, 
+            investor_redemptions_account, 
+            investor.key, 
+            &fund_name
+        )?;
+
+        investor_redemptions.close_request(share_redemption_data.request_id)?;
+
+        fund_account_data.accrue_management_fee(mint_account, Clock::get()?.unix_timestamp)?;
+
+        fund_account_data.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
+
+        move_shares(
+            token_program, 
+            mint_account, 
+            fund_vault, 
+            investor_ata, 
+            fund_account, 
+            fund_account, 
+            &[
+                b"fund_account",
+                fund_name.as_bytes(),
+                &[fund_account_data.bump_seed]
+            ], 
+            share_redemption_data.shares_amount
+        )?;
+
+        Self::close_share_redemption_account(share_redemption_account, punto_xero)?;
+
+        investor_redemptions.save(investor_redemptions_account)?;
+
+        msg!("cancelled redemption request {}", share_redemption_data.request_id);
+
+        Ok(())
+    }
+
+    pub fn escalate_share_redemption(
+        program_id: &Pubkey,
+        fund_account: &AccountInfo,
+        share_redemption_account: &AccountInfo,
+        investor: &AccountInfo,
+        fund_name: String,
+    ) -> ProgramResult {
+
+        if !investor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let fund_account_pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]], 
+            program_id
+        )?;
+
+        if fund_account_pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if share_redemption_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut share_redemption_data = try_from_slice_unchecked::<ShareRedemption>(
+            &share_redemption_account.data.borrow()[..]
+        )?;
+
+        let share_redemption_pda = Pubkey::create_program_address(
+            &[
+                b"share_redemption", 
+                fund_name.as_bytes(), 
+                &investor.key.to_bytes(), 
+                &share_redemption_data.request_id.to_le_bytes(),
+                &[share_redemption_data.bump_seed]
+            ], 
+            program_id
+        )?;
+
+        if share_redemption_pda != *share_redemption_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if share_redemption_data.is_overdue {
+            return Err(CustomError::RedemptionAlreadyEscalated.into());
+        }
+
+        let deadline_seconds = fund_account_data.config.settlement_deadline_seconds;
+
+        if deadline_seconds <= 0 {
+            return Err(CustomError::SettlementDeadlineNotReached.into());
+        }
+
+        let deadline_timestamp = share_redemption_data.created_timestamp
+            .checked_add(deadline_seconds)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        if current_timestamp <= deadline_timestamp {
+            return Err(CustomError::SettlementDeadlineNotReached.into());
+        }
+
+        share_redemption_data.is_overdue = true;
+        share_redemption_data.escalated_timestamp = current_timestamp;
+
+        share_redemption_data.serialize(&mut &mut share_redemption_account.data.borrow_mut()[..])?;
+
+        msg!(
+            "redemption overdue: fund {} investor {} request {} shares {} deadline {} escalated {}", 
+            fund_account.key,
+            investor.key,
+            share_redemption_data.request_id,
+            share_redemption_data.shares_amount,
+            deadline_timestamp,
+            current_timestamp
+        );
+
+        Ok(())
+    }
+
+    fn close_share_redemption_account(
+        share_redemption_account: &AccountInfo,
+        rent_destination: &AccountInfo,
+    ) -> ProgramResult {
         let empty_account_span = 0usize;
         let lamports_required = (Rent::get()?).minimum_balance(empty_account_span);
         let diff = share_redemption_account.lamports() - lamports_required;
         **share_redemption_account.lamports.borrow_mut() -= diff;
-        **punto_xero.lamports.borrow_mut() += diff;
+        **rent_destination.lamports.borrow_mut() += diff;
         share_redemption_account.realloc(empty_account_span, true)?;
-        share_redemption_account.assign(system_program.key);
+        share_redemption_account.assign(&SYSTEM_PROGRAM_ID);
 
         Ok(())
     }
@@ -0,0 +1,137 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    entrypoint::ProgramResult, 
+    instruction::Instruction, 
+    program::invoke_signed, 
+    program_error::ProgramError, 
+    pubkey::Pubkey, 
+};
+use spl_token_2022::{
+    extension::{transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions},
+    instruction::{burn, mint_to},
+    state::{Account as TokenAccount, Mint},
+};
+
+// Fund mints are either classic SPL Token mints or Token-2022 mints with a
+// transfer hook, so share accounts are read through the Token-2022 layout,
+// which is a superset of the classic one.
+pub fn check_token_program(token_program: &AccountInfo) -> ProgramResult {
+    if *token_program.key != spl_token::ID && *token_program.key != spl_token_2022::ID {
+        return Err(ProgramError::InvalidAccountData)
+    }
+
+    Ok(())
+}
+
+pub fn unpack_mint(mint_account: &AccountInfo) -> Result<Mint, ProgramError> {
+    Ok(StateWithExtensions::<Mint>::unpack(&mint_account.data.borrow())?.base)
+}
+
+pub fn unpack_token_account(token_account: &AccountInfo) -> Result<TokenAccount, ProgramError> {
+    Ok(StateWithExtensions::<TokenAccount>::unpack(&token_account.data.borrow())?.base)
+}
+
+// Token-2022 deprecates plain transfers in favour of transfer_checked, but they
+// remain valid for mints without a hook or fee, which is all this is used for
+#[allow(deprecated)]
+pub fn transfer(
+    token_program_id: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    signers: &[&Pubkey],
+    amount: u64
+) -> Result<Instruction, ProgramError> {
+    spl_token_2022::instruction::transfer(
+        token_program_id, 
+        source, 
+        destination, 
+        authority, 
+        signers, 
+        amount
+    )
+}
+
+pub fn has_transfer_hook(mint_account: &AccountInfo) -> Result<bool, ProgramError> {
+    let mint_data = mint_account.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+
+    Ok(mint.get_extension::<TransferHook>().is_ok())
+}
+
+// Moves shares on behalf of the program. A transfer on a hooked mint would
+// call back into this program, which the runtime rejects as reentrancy, so
+// those moves burn from the source and mint to the destination instead.
+pub fn move_shares<'a>(
+    token_program: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    fund_account: &AccountInfo<'a>,
+    fund_account_seeds: &[&[u8]],
+    amount: u64
+) -> ProgramResult {
+
+    if !has_transfer_hook(mint_account)? {
+        let transfer_ix = transfer(
+            token_program.key, 
+            source.key, 
+            destination.key, 
+            authority.key, 
+            &[authority.key], 
+            amount
+        )?;
+
+        return invoke_signed(
+            &transfer_ix, 
+            &[
+                token_program.clone(),
+                source.clone(),
+                destination.clone(),
+                authority.clone()
+            ], 
+            &[fund_account_seeds]
+        );
+    }
+
+    let burn_ix = burn(
+        token_program.key, 
+        source.key, 
+        mint_account.key, 
+        authority.key, 
+        &[authority.key], 
+        amount
+    )?;
+
+    invoke_signed(
+        &burn_ix, 
+        &[
+            token_program.clone(),
+            source.clone(),
+            mint_account.clone(),
+            authority.clone()
+        ], 
+        &[fund_account_seeds]
+    )?;
+
+    let mint_to_ix = mint_to(
+        token_program.key, 
+        mint_account.key, 
+        destination.key, 
+        fund_account.key, 
+        &[fund_account.key], 
+        amount
+    )?;
+
+    invoke_signed(
+        &mint_to_ix, 
+        &[
+            token_program.clone(),
+            mint_account.clone(),
+            destination.clone(),
+            fund_account.clone()
+        ], 
+        &[fund_account_seeds]
+    )
+}
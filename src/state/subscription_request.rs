@@ -0,0 +1,655 @@
+use solana_program::{
+    pubkey::Pubkey,
+    program_pack::{IsInitialized, Sealed}, 
+    entrypoint::ProgramResult,
+    account_info::AccountInfo,
+    system_program::ID as SYSTEM_PROGRAM_ID,
+    program_error::ProgramError,
+    borsh1::try_from_slice_unchecked, 
+    program::{invoke_signed, invoke},
+    system_instruction::create_account, 
+    sysvar::Sysvar,
+    rent::Rent,
+    clock::Clock, 
+    msg
+};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    ID as ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::errors::CustomError;
+
+use super::{
+    fund_account::FundAccount, 
+    investor_record::InvestorRecord, 
+    nav_history::NavHistory, 
+    share_token::{check_token_program, transfer}
+};
+
+// Subscription queued under forward pricing. Shares are only minted once the
+// manager processes the request against a share value struck after it was made.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct SubscriptionRequest {
+    pub is_initialized: bool,
+    pub bump_seed: u8,
+    pub fund_account: Pubkey,
+    pub investor: Pubkey,
+    pub request_id: u64,
+    pub amount_in_fiat: u64,
+    pub escrowed: bool,
+    pub created_timestamp: i64,
+    pub dealing_timestamp: i64,
+}
+
+impl Sealed for SubscriptionRequest {}
+
+impl IsInitialized for SubscriptionRequest {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl SubscriptionRequest {
+
+    const LEN: usize = 1      // for is_initialized
+        + 1                   // for bump_seed
+        + 32                  // for fund_account
+        + 32                  // for investor
+        + 8                   // for request_id
+        + 8                   // for amount_in_fiat
+        + 1                   // for escrowed
+        + 8                   // for created_timestamp
+        + 8;                  // for dealing_timestamp
+
+    pub fn create_subscription_request<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        subscription_request_account: &AccountInfo<'a>,
+        investor: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        payment_accounts: Option<(&AccountInfo<'a>, &AccountInfo<'a>, &AccountInfo<'a>)>,
+        investor_record_account: Option<&AccountInfo<'a>>,
+        amount_in_fiat: u64,
+        fund_name: String,
+    ) -> ProgramResult {
+
+        if *system_program.key != SYSTEM_PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        check_token_program(token_program)?;
+
+        if !punto_xero.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !investor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if amount_in_fiat == 0 {
+            return Err(CustomError::InvalidSubscriptionAmount.into());
+        }
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let fund_account_pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]], 
+            program_id
+        )?;
+
+        if fund_account_pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.punto_xero_master_pubkey != *punto_xero.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        InvestorRecord::check_investor(
+            program_id, 
+            fund_account.key, 
+            &fund_account_data, 
+            investor_record_account, 
+            investor.key, 
+            current_timestamp
+        )?;
+
+        let dealing_timestamp = fund_account_data.config.dealing_schedule
+            .dealing_timestamp_for(current_timestamp)?;
+
+        let request_id = fund_account_data.next_subscription_request_id;
+
+        fund_account_data.next_subscription_request_id = request_id.checked_add(1)
+            .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+        let (subscription_request_pda, subscription_request_bump) = Pubkey::find_program_address(
+            &[
+                b"subscription_request", 
+                fund_name.as_bytes(), 
+                &request_id.to_le_bytes()
+            ], 
+            program_id
+        );
+
+        if subscription_request_pda != *subscription_request_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let rent = Rent::get()?.minimum_balance(Self::LEN);
+
+        invoke_signed(
+            &create_account(
+                punto_xero.key, 
+                subscription_request_account.key, 
+                rent, 
+                Self::LEN as u64, 
+                program_id
+            ), 
+            &[
+                punto_xero.clone(),
+                subscription_request_account.clone(),
+                system_program.clone()
+            ], 
+            &[
+                &[
+                    b"subscription_request",
+                    fund_name.as_bytes(),
+                    &request_id.to_le_bytes(),
+                    &[subscription_request_bump]
+                ]
+            ]
+        )?;
+
+        if let Some(settlement_mint) = fund_account_data.settlement_mint {
+            let Some((investor_payment_account, cash_vault, settlement_token_program)) = payment_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            fund_account_data.check_settlement_token_program(settlement_token_program)?;
+
+            let investor_payment_address = get_associated_token_address_with_program_id(
+                investor.key, 
+                &settlement_mint, 
+                settlement_token_program.key
+            );
+
+            if investor_payment_address != *investor_payment_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if fund_account_data.cash_vault != Some(*cash_vault.key) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let payment_ix = transfer(
+                settlement_token_program.key, 
+                investor_payment_account.key, 
+                cash_vault.key, 
+                investor.key, 
+                &[investor.key], 
+                amount_in_fiat
+            )?;
+
+            invoke(
+                &payment_ix, 
+                &[
+                    settlement_token_program.clone(),
+                    investor_payment_account.clone(),
+                    cash_vault.clone(),
+                    investor.clone()
+                ]
+            )?;
+        }
+
+        let subscription_request = SubscriptionRequest {
+            is_initialized: true,
+            bump_seed: subscription_request_bump,
+            fund_account: *fund_account.key,
+            investor: *investor.key,
+            request_id,
+            amount_in_fiat,
+            escrowed: fund_account_data.settlement_mint.is_some(),
+            created_timestamp: current_timestamp,
+            dealing_timestamp,
+        };
+
+        subscription_request.serialize(&mut &mut subscription_request_account.data.borrow_mut()[..])?;
+
+        fund_account_data.serialize(&mut &mut fund_account.data.borrow_mut()[..])?;
+
+        msg!("opened subscription request {}", request_id);
+
+        Ok(())
+    }
+
+    pub fn process_subscription_request<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        manager_master: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        mint_account: &AccountInfo<'a>,
+        subscription_request_account: &AccountInfo<'a>,
+        investor: &AccountInfo<'a>,
+        investor_ata: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        associated_token_account_program: &AccountInfo<'a>,
+        investor_lots_account: Option<&AccountInfo<'a>>,
+        nav_history_account: &AccountInfo<'a>,
+        investor_record_account: Option<&AccountInfo<'a>>,
+        fund_name: String,
+    ) -> ProgramResult {
+
+        if !punto_xero.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !manager_master.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if *system_program.key != SYSTEM_PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        check_token_program(token_program)?;
+
+        if *associated_token_account_program.key != ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let mut fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let fund_account_pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]], 
+            program_id
+        )?;
+
+        if fund_account_pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.punto_xero_master_pubkey != *punto_xero.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account_data.manager_master_pubkey != *manager_master.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account_data.fund_mint != *mint_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if subscription_request_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let subscription_request = try_from_slice_unchecked::<SubscriptionRequest>(
+            &subscription_request_account.data.borrow()[..]
+        )?;
+
+        let subscription_request_pda = Pubkey::create_program_address(
+            &[
+                b"subscription_request", 
+                fund_name.as_bytes(), 
+                &subscription_request.request_id.to_le_bytes(),
+                &[subscription_request.bump_seed]
+            ], 
+            program_id
+        )?;
+
+        if subscription_request_pda != *subscription_request_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if subscription_request.investor != *investor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        if current_timestamp < subscription_request.dealing_timestamp {
+            return Err(CustomError::BeforeDealingDate.into());
+        }
+
+        // the investor may have been suspended or lapsed since requesting
+        InvestorRecord::check_investor(
+            program_id, 
+            fund_account.key, 
+            &fund_account_data, 
+            investor_record_account, 
+            investor.key, 
+            current_timestamp
+        )?;
+
+        // priced at the first share value struck after the dealing date
+        let dealing_share_value = NavHistory::load(program_id, &fund_account_data, nav_history_account)?
+            .first_after(subscription_request.dealing_timestamp)?
+            .share_value;
+
+        fund_account_data.accrue_management_fee(mint_account, current_timestamp)?;
+
+        let shares_issued = fund_account_data.issue_shares(
+            program_id,
+            punto_xero, 
+            fund_account, 
+            mint_account, 
+            investor, 
+            investor_ata, 
+            system_program, 
+            token_program, 
+            associated_token_account_program, 
+            investor_lots_account,
+            subscription_request.amount_in_fiat, 
+            dealing_share_value,
+            &fund_name
+        )?;
+
+        msg!(
+            "subscription request {}: {} shares for {} at {}", 
+            subscription_request.request_id,
+            shares_issued,
+            subscription_request.amount_in_fiat,
+            dealing_share_value
+        );
+
+        Self::close_subscription_request_account(subscription_request_account, punto_xero)?;
+
+        Ok(())
+    }
+
+    pub fn cancel_subscription_request<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        subscription_request_account: &AccountInfo<'a>,
+        investor: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        payment_accounts: Option<(&AccountInfo<'a>, &AccountInfo<'a>, &AccountInfo<'a>)>,
+        fund_name: String,
+    ) -> ProgramResult {
+
+        if !investor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        check_token_program(token_program)?;
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let fund_account_pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]], 
+            program_id
+        )?;
+
+        if fund_account_pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.punto_xero_master_pubkey != *punto_xero.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.config.cancellation_requires_platform_signature && !punto_xero.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if subscription_request_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let subscription_request = try_from_slice_unchecked::<SubscriptionRequest>(
+            &subscription_request_account.data.borrow()[..]
+        )?;
+
+        let subscription_request_pda = Pubkey::create_program_address(
+            &[
+                b"subscription_request", 
+                fund_name.as_bytes(), 
+                &subscription_request.request_id.to_le_bytes(),
+                &[subscription_request.bump_seed]
+            ], 
+            program_id
+        )?;
+
+        if subscription_request_pda != *subscription_request_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if subscription_request.investor != *investor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let cutoff_seconds = fund_account_data.config.cancellation_cutoff_seconds;
+        let deadline_seconds = fund_account_data.config.settlement_deadline_seconds;
+
+        // past the cutoff the investor can only walk away once the manager
+        // has missed the settlement deadline
+        if cutoff_seconds > 0 {
+            let cutoff_timestamp = subscription_request.created_timestamp
+                .checked_add(cutoff_seconds)
+                .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+            let deadline_passed = deadline_seconds > 0 && current_timestamp > subscription_request.created_timestamp
+                .checked_add(deadline_seconds)
+                .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+
+            if current_timestamp > cutoff_timestamp && !deadline_passed {
+                return Err(CustomError::CancellationWindowClosed.into());
+            }
+        }
+
+        Self::refund_escrow(
+            fund_account, 
+            &fund_account_data, 
+            &subscription_request, 
+            investor, 
+            payment_accounts, 
+            &fund_name
+        )?;
+
+        Self::close_subscription_request_account(subscription_request_account, punto_xero)?;
+
+        msg!("cancelled subscription request {}", subscription_request.request_id);
+
+        Ok(())
+    }
+
+    // lets the manager hand back a request that can no longer be processed,
+    // e.g. once the investor has been suspended or would breach a holding limit
+    pub fn reject_subscription_request<'a>(
+        program_id: &Pubkey,
+        punto_xero: &AccountInfo<'a>,
+        manager_master: &AccountInfo<'a>,
+        fund_account: &AccountInfo<'a>,
+        subscription_request_account: &AccountInfo<'a>,
+        investor: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        payment_accounts: Option<(&AccountInfo<'a>, &AccountInfo<'a>, &AccountInfo<'a>)>,
+        fund_name: String,
+    ) -> ProgramResult {
+
+        if !punto_xero.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !manager_master.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        check_token_program(token_program)?;
+
+        if fund_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let fund_account_data = try_from_slice_unchecked::<FundAccount>(
+            &fund_account.data.borrow()[..]
+        )?;
+
+        let fund_account_pda = Pubkey::create_program_address(
+            &[b"fund_account", fund_name.as_bytes(), &[fund_account_data.bump_seed]], 
+            program_id
+        )?;
+
+        if fund_account_pda != *fund_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if fund_account_data.punto_xero_master_pubkey != *punto_xero.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if fund_account_data.manager_master_pubkey != *manager_master.key {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if subscription_request_account.owner != program_id {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let subscription_request = try_from_slice_unchecked::<SubscriptionRequest>(
+            &subscription_request_account.data.borrow()[..]
+        )?;
+
+        let subscription_request_pda = Pubkey::create_program_address(
+            &[
+                b"subscription_request", 
+                fund_name.as_bytes(), 
+                &subscription_request.request_id.to_le_bytes(),
+                &[subscription_request.bump_seed]
+            ], 
+            program_id
+        )?;
+
+        if subscription_request_pda != *subscription_request_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if subscription_request.investor != *investor.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Self::refund_escrow(
+            fund_account, 
+            &fund_account_data, 
+            &subscription_request, 
+            investor, 
+            payment_accounts, 
+            &fund_name
+        )?;
+
+        Self::close_subscription_request_account(subscription_request_account, punto_xero)?;
+
+        msg!("rejected subscription request {}", subscription_request.request_id);
+
+        Ok(())
+    }
+
+    fn refund_escrow<'a>(
+        fund_account: &AccountInfo<'a>,
+        fund_account_data: &FundAccount,
+        subscription_request: &SubscriptionRequest,
+        investor: &AccountInfo<'a>,
+        payment_accounts: Option<(&AccountInfo<'a>, &AccountInfo<'a>, &AccountInfo<'a>)>,
+        fund_name: &str,
+    ) -> ProgramResult {
+
+        if subscription_request.escrowed {
+            let Some(settlement_mint) = fund_account_data.settlement_mint else {
+                return Err(ProgramError::InvalidAccountData);
+            };
+
+            let Some((investor_payment_account, cash_vault, settlement_token_program)) = payment_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+
+            fund_account_data.check_settlement_token_program(settlement_token_program)?;
+
+            let investor_payment_address = get_associated_token_address_with_program_id(
+                investor.key, 
+                &settlement_mint, 
+                settlement_token_program.key
+            );
+
+            if investor_payment_address != *investor_payment_account.key {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if fund_account_data.cash_vault != Some(*cash_vault.key) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let refund_ix = transfer(
+                settlement_token_program.key, 
+                cash_vault.key, 
+                investor_payment_account.key, 
+                fund_account.key, 
+                &[fund_account.key], 
+                subscription_request.amount_in_fiat
+            )?;
+
+            invoke_signed(
+                &refund_ix, 
+                &[
+                    settlement_token_program.clone(),
+                    cash_vault.clone(),
+                    investor_payment_account.clone(),
+                    fund_account.clone(),
+                ], 
+                &[
+                    &[
+                        b"fund_account",
+                        fund_name.as_bytes(),
+                        &[fund_account_data.bump_seed]
+                    ]
+                ]
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn close_subscription_request_account(
+        subscription_request_account: &AccountInfo,
+        rent_destination: &AccountInfo,
+    ) -> ProgramResult {
+        let empty_account_span = 0usize;
+        let lamports_required = (Rent::get()?).minimum_balance(empty_account_span);
+        let diff = subscription_request_account.lamports() - lamports_required;
+        **subscription_request_account.lamports.borrow_mut() -= diff;
+        **rent_destination.lamports.borrow_mut() += diff;
+        subscription_request_account.realloc(empty_account_span, true)?;
+        subscription_request_account.assign(&SYSTEM_PROGRAM_ID);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use solana_program::{
+    account_info::AccountInfo, 
+    borsh1::try_from_slice_unchecked, 
+    clock::Clock, 
+    entrypoint::ProgramResult, 
+    program::invoke_signed, 
+    program_error::ProgramError, 
+    pubkey::Pubkey, 
+    rent::Rent, 
+    system_instruction::create_account, 
+    sysvar::Sysvar,
+};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, 
+    seeds::Seed, 
+    state::ExtraAccountMetaList
+};
+use spl_transfer_hook_interface::{
+    collect_extra_account_metas_signer_seeds, 
+    get_extra_account_metas_address_and_bump_seed, 
+    instruction::ExecuteInstruction
+};
+
+use super::{
+    fund_account::{FundAccount, ShareTransferPolicy}, 
+    investor_record::InvestorRecord, 
+    share_token::unpack_token_account
+};
+
+use crate::errors::CustomError;
+
+// Execute receives [source, mint, destination, authority, extra_account_metas]
+// followed by the fund account and the investor records of both owners.
+const SOURCE_INDEX: u8 = 0;
+const DESTINATION_INDEX: u8 = 2;
+const FUND_ACCOUNT_INDEX: u8 = 5;
+
+// owner field of a token account
+const OWNER_OFFSET: u8 = 32;
+
+fn investor_record_meta(token_account_index: u8) -> Result<ExtraAccountMeta, ProgramError> {
+    ExtraAccountMeta::new_with_seeds(
+        &[
+            Seed::Literal { bytes: b"investor_record".to_vec() },
+            Seed::AccountKey { index: FUND_ACCOUNT_INDEX },
+            Seed::AccountData { 
+                account_index: token_account_index, 
+                data_index: OWNER_OFFSET, 
+                length: 32 
+            },
+        ], 
+        false, 
+        false
+    )
+}
+
+pub fn initialize_extra_account_metas<'a>(
+    program_id: &Pubkey,
+    punto_xero: &AccountInfo<'a>,
+    fund_account: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    extra_account_metas_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+
+    let (extra_account_metas_pda, bump_seed) = get_extra_account_metas_address_and_bump_seed(
+        mint_account.key, 
+        program_id
+    );
+
+    if extra_account_metas_pda != *extra_account_metas_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let extra_account_metas = [
+        ExtraAccountMeta::new_with_pubkey(fund_account.key, false, false)?,
+        investor_record_meta(SOURCE_INDEX)?,
+        investor_record_meta(DESTINATION_INDEX)?,
+    ];
+
+    let space = ExtraAccountMetaList::size_of(extra_account_metas.len())?;
+    let rent = Rent::get()?.minimum_balance(space);
+
+    invoke_signed(
+        &create_account(
+            punto_xero.key, 
+            extra_account_metas_account.key, 
+            rent, 
+            space as u64, 
+            program_id
+        ), 
+        &[
+            punto_xero.clone(),
+            extra_account_metas_account.clone(),
+            system_program.clone()
+        ], 
+        &[&collect_extra_account_metas_signer_seeds(mint_account.key, &[bump_seed])]
+    )?;
+
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut extra_account_metas_account.data.borrow_mut(), 
+        &extra_account_metas
+    )?;
+
+    Ok(())
+}
+
+pub fn check_share_transfer<'a>(
+    program_id: &Pubkey,
+    source: &AccountInfo<'a>,
+    mint_account: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    fund_account: &AccountInfo<'a>,
+    source_record: &AccountInfo<'a>,
+    destination_record: &AccountInfo<'a>,
+) -> ProgramResult {
+
+    if fund_account.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let fund_account_data = try_from_slice_unchecked::<FundAccount>(
+        &fund_account.data.borrow()[..]
+    )?;
+
+    if fund_account_data.fund_mint != *mint_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if fund_account_data.config.share_transfer_policy == ShareTransferPolicy::Blocked {
+        return Err(CustomError::ShareTransfersBlocked.into());
+    }
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    for (token_account, investor_record_account) in [
+        (source, source_record),
+        (destination, destination_record),
+    ] {
+        let owner = unpack_token_account(token_account)?.owner;
+
+        InvestorRecord::check_approved(
+            program_id, 
+            fund_account.key, 
+            investor_record_account, 
+            &owner, 
+            current_timestamp
+        )?;
+    }
+
+    Ok(())
+}
@@ -2,8 +2,33 @@
 mod tests {
     use std::sync::Arc;
 
-    use instructions::{BuyFundSharesPayload, FundInitPayload, ProcessSharesRedemptionPayload, RedeemSharesPayload, UpdateShareValuePayload};
-    use solana_program_test::{tokio::{self, sync::Mutex}, BanksClient, ProgramTest};
+    use instructions::{
+        AccrueFeesPayload, 
+        AddInvestorPayload, 
+        ApproveShareValuePayload, 
+        BuyFundSharesPayload, 
+        CancelRedemptionPayload, 
+        CancelSubscriptionPayload, 
+        RejectSubscriptionPayload, 
+        EscalateRedemptionPayload, 
+        FundInitPayload, 
+        InitNavHistoryPayload, 
+        InvestorStatusPayload, 
+        ProcessSharesRedemptionPayload, 
+        ProcessSubscriptionPayload, 
+        RedeemSharesPayload, 
+        RefreshShareValueFromOraclePayload, 
+        UpdateFundConfigPayload, 
+        UpdateShareMetadataPayload, 
+        UpdateShareValuePayload
+    };
+    use solana_program_test::{
+        tokio::{self, sync::Mutex}, 
+        BanksClient, 
+        BanksClientError, 
+        ProgramTest, 
+        ProgramTestContext
+    };
     use solana_sdk::{
         hash::Hash, 
         instruction::{AccountMeta, Instruction}, 
@@ -13,18 +38,39 @@ mod tests {
         system_program::ID as SYSTEM_PROGRAM_ID, 
         sysvar::rent::ID as RENT_SYSVAR_ID, 
         transaction::Transaction,
-        program_pack::Pack
+        program_pack::Pack,
+        system_instruction::create_account,
+        instruction::InstructionError,
+        transaction::TransactionError,
+        clock::Clock,
+        account::{Account, AccountSharedData}
     };
     use spl_associated_token_account::{
         get_associated_token_address,
+        get_associated_token_address_with_program_id,
+        instruction::create_associated_token_account,
         ID as ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID
     };
     use spl_token::{
         ID as TOKEN_PROGRAM_ID,
-        state::Account as TokenAccount
+        instruction::{initialize_mint, mint_to},
+        state::{Account as TokenAccount, Mint}
     };
-    use borsh::{BorshDeserialize, BorshSerialize};
-    use crate::*;
+    use spl_token_2022::{
+        ID as TOKEN_2022_PROGRAM_ID,
+        extension::{transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions},
+        instruction::transfer_checked,
+        state::{Account as Token2022Account, Mint as Token2022Mint}
+    };
+    use spl_transfer_hook_interface::get_extra_account_metas_address;
+    use spl_token_metadata_interface::state::TokenMetadata;
+    use solana_program::borsh1::try_from_slice_unchecked;
+    use borsh::BorshSerialize;
+    use crate::{*, errors::CustomError, state::{
+        dealing_calendar::{DealingFrequency, DealingSchedule},
+        fund_account::{FundConfig, PricingMode, ShareTransferPolicy}, 
+        price_feed::PriceFeed
+    }};
 
     struct TestSetup {
         pub program_id: Pubkey,
@@ -36,12 +82,18 @@ mod tests {
         pub fund_vault: Pubkey,
         pub banks_client: Arc<Mutex<BanksClient>>,
         pub recent_blockhash: Hash,
-        pub fund_name: String
+        pub fund_name: String,
+        pub token_program: Pubkey,
+        pub context: Arc<Mutex<ProgramTestContext>>
     }
 
     const SCALE_FACTOR: u64 = 1_000_000;
 
     async fn get_setup() -> TestSetup {
+        get_setup_with_token_program(TOKEN_PROGRAM_ID).await
+    }
+
+    async fn get_setup_with_token_program(token_program: Pubkey) -> TestSetup {
         solana_logger::setup_with_default("solana_program::message=debug");
 
         let program_id = Pubkey::new_unique();
@@ -56,9 +108,10 @@ mod tests {
             &[b"fund_mint", fund_name.as_bytes()], 
             &program_id
         );
-        let fund_vault = get_associated_token_address(
+        let fund_vault = get_associated_token_address_with_program_id(
             &fund_account_pda, 
-            &fund_mint_account
+            &fund_mint_account,
+            &token_program
         );
 
         let program_test = ProgramTest::new(
@@ -67,11 +120,11 @@ mod tests {
             None
         );
 
-        let (
-            banks_client, 
-            punto_xero_master, 
-            recent_blockhash
-        ) = program_test.start().await;
+        let context = program_test.start_with_context().await;
+
+        let banks_client = context.banks_client.clone();
+        let punto_xero_master = context.payer.insecure_clone();
+        let recent_blockhash = context.last_blockhash;
 
         TestSetup {
             program_id,
@@ -83,11 +136,45 @@ mod tests {
             fund_vault,
             banks_client: Arc::new(Mutex::new(banks_client)),
             recent_blockhash,
-            fund_name
+            fund_name,
+            token_program,
+            context: Arc::new(Mutex::new(context))
         }
     }
 
     async fn initialize_fund_and_mint(setup: &TestSetup) {
+        initialize_fund_with_extra_accounts(setup, vec![]).await;
+    }
+
+    async fn initialize_fund_with_extra_accounts(
+        setup: &TestSetup,
+        extra_accounts: Vec<AccountMeta>
+    ) {
+        initialize_fund_with_share_decimals(setup, 6, extra_accounts).await;
+    }
+
+    async fn initialize_fund_with_share_decimals(
+        setup: &TestSetup,
+        share_decimals: u8,
+        extra_accounts: Vec<AccountMeta>
+    ) {
+        // only Token-2022 fund mints carry metadata
+        let (symbol, uri) = if setup.token_program == TOKEN_2022_PROGRAM_ID {
+            (String::from("PXF"), String::from("https://puntoxero.com/fund.json"))
+        } else {
+            (String::new(), String::new())
+        };
+
+        initialize_fund_request(setup, share_decimals, symbol, uri, extra_accounts).await.unwrap();
+    }
+
+    async fn initialize_fund_request(
+        setup: &TestSetup,
+        share_decimals: u8,
+        symbol: String,
+        uri: String,
+        extra_accounts: Vec<AccountMeta>
+    ) -> Result<(), BanksClientError> {
         let TestSetup {
             program_id,
             punto_xero_master,
@@ -98,32 +185,42 @@ mod tests {
             fund_vault,
             banks_client,
             recent_blockhash,
-            fund_name
+            fund_name,
+            token_program: _,
+            context: _
         } = setup;
 
         let mut banks_client = banks_client.lock().await;
 
-        let initialize_payload 
-            = FundInitPayload { share_value: 10_000 * SCALE_FACTOR, fund_name: fund_name.clone()};
+        let initialize_payload = FundInitPayload { 
+            share_value: 10_000 * SCALE_FACTOR, 
+            share_decimals,
+            symbol,
+            uri,
+            fund_name: fund_name.clone()
+        };
 
         let mut initialize_payload_data = Vec::new();
         initialize_payload.serialize(&mut initialize_payload_data)
             .unwrap();
 
+        let mut accounts = vec![
+            AccountMeta::new(punto_xero_master.pubkey(), true),
+            AccountMeta::new_readonly(fund_manager_master.pubkey(), true),
+            AccountMeta::new(*fund_account, false),
+            AccountMeta::new(*fund_mint, false),
+            AccountMeta::new(*fund_vault, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(setup.token_program, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, false),
+            AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+        ];
+        accounts.extend(extra_accounts);
+
         let instruction = Instruction::new_with_bytes(
             *program_id, 
             &[&[0][..], &initialize_payload_data].concat(),
-            vec![
-                AccountMeta::new(punto_xero_master.pubkey(), true),
-                AccountMeta::new_readonly(fund_manager_master.pubkey(), true),
-                AccountMeta::new(*fund_account, false),
-                AccountMeta::new(*fund_mint, false),
-                AccountMeta::new(*fund_vault, false),
-                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
-                AccountMeta::new_readonly(ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, false),
-                AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
-            ]
+            accounts
         );
 
         let transaction = Transaction::new_signed_with_payer(
@@ -136,7 +233,7 @@ mod tests {
             *recent_blockhash
         );
 
-        banks_client.process_transaction(transaction).await.unwrap();
+        banks_client.process_transaction(transaction).await
     }
 
     
@@ -153,7 +250,7 @@ mod tests {
             .unwrap()
             .unwrap();
 
-        let fund_account_data = state::fund_account::FundAccount::try_from_slice(&fund_account_info.data)
+        let fund_account_data = try_from_slice_unchecked::<state::fund_account::FundAccount>(&fund_account_info.data)
             .unwrap();
 
         assert_eq!(&fund_account_data.fund_name, &setup.fund_name);
@@ -186,6 +283,7 @@ mod tests {
                 AccountMeta::new(setup.punto_xero_master.pubkey(), true),
                 AccountMeta::new_readonly(setup.fund_manager_master.pubkey(), true),
                 AccountMeta::new(setup.fund_account, false),
+                AccountMeta::new_readonly(setup.fund_mint, false),
             ]
         );
 
@@ -207,7 +305,7 @@ mod tests {
             .unwrap()
             .unwrap();
 
-        let fund_account_data = state::fund_account::FundAccount::try_from_slice(&fund_account_info.data)
+        let fund_account_data = try_from_slice_unchecked::<state::fund_account::FundAccount>(&fund_account_info.data)
             .unwrap();
 
         assert_eq!(fund_account_data.share_value, new_share_value);
@@ -218,6 +316,21 @@ mod tests {
     async fn buy_shares(
         setup: &TestSetup
     ) {
+        buy_shares_with_extra_accounts(setup, vec![]).await.unwrap();
+    }
+
+    async fn buy_shares_with_extra_accounts(
+        setup: &TestSetup,
+        extra_accounts: Vec<AccountMeta>
+    ) -> Result<(), BanksClientError> {
+        buy_shares_request(setup, 20_000 * SCALE_FACTOR, extra_accounts).await
+    }
+
+    async fn buy_shares_request(
+        setup: &TestSetup,
+        amount_in_fiat: u64,
+        extra_accounts: Vec<AccountMeta>
+    ) -> Result<(), BanksClientError> {
         let TestSetup {
             program_id,
             punto_xero_master,
@@ -228,33 +341,42 @@ mod tests {
             fund_vault: _,
             banks_client,
             recent_blockhash,
-            fund_name
+            fund_name,
+            token_program: _,
+            context: _
         } = setup;
 
         let mut banks_client = banks_client.lock().await;
 
-        let buyer_ata = get_associated_token_address(&buyer.pubkey(), &fund_mint);
+        let buyer_ata = get_associated_token_address_with_program_id(
+            &buyer.pubkey(), 
+            &fund_mint, 
+            &setup.token_program
+        );
 
         let buy_shares_payload 
-            = BuyFundSharesPayload { amount_in_fiat: 20_000 * SCALE_FACTOR, fund_name: fund_name.clone() };
+            = BuyFundSharesPayload { amount_in_fiat, fund_name: fund_name.clone() };
 
         let mut buy_shares_payload_data = Vec::new();
         buy_shares_payload.serialize(&mut buy_shares_payload_data)
             .unwrap();
 
+        let mut accounts = vec![
+            AccountMeta::new(punto_xero_master.pubkey(), true),
+            AccountMeta::new(*fund_account, false),
+            AccountMeta::new(*fund_mint, false),
+            AccountMeta::new(buyer.pubkey(), true),
+            AccountMeta::new(buyer_ata, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(setup.token_program, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, false),
+        ];
+        accounts.extend(extra_accounts);
+
         let instruction = Instruction::new_with_bytes(
             *program_id, 
             &[&[2][..], &buy_shares_payload_data].concat(),
-            vec![
-                AccountMeta::new(punto_xero_master.pubkey(), true),
-                AccountMeta::new(*fund_account, false),
-                AccountMeta::new(*fund_mint, false),
-                AccountMeta::new(buyer.pubkey(), true),
-                AccountMeta::new(buyer_ata, false),
-                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
-                AccountMeta::new_readonly(ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, false),
-            ]
+            accounts
         );
 
         let transaction = Transaction::new_signed_with_payer(
@@ -267,7 +389,7 @@ mod tests {
             *recent_blockhash
         );
 
-        banks_client.process_transaction(transaction).await.unwrap();
+        banks_client.process_transaction(transaction).await
     }
 
     #[tokio::test]
@@ -288,7 +410,9 @@ mod tests {
             fund_vault: _,
             banks_client,
             recent_blockhash: _,
-            fund_name: _
+            fund_name: _,
+            token_program: _,
+            context: _
         } = setup;
 
 
@@ -309,8 +433,43 @@ mod tests {
 
     }
 
+    fn get_share_redemption_address(setup: &TestSetup, request_id: u64) -> Pubkey {
+        let (share_redemption, _bump_seed) = Pubkey::find_program_address(
+            &[
+                b"share_redemption", 
+                setup.fund_name.as_bytes(), 
+                &setup.buyer.pubkey().to_bytes(), 
+                &request_id.to_le_bytes()
+            ], 
+            &setup.program_id
+        );
+
+        share_redemption
+    }
+
+    fn get_investor_redemptions_address(setup: &TestSetup) -> Pubkey {
+        let (investor_redemptions, _bump_seed) = Pubkey::find_program_address(
+            &[
+                b"investor_redemptions", 
+                setup.fund_name.as_bytes(), 
+                &setup.buyer.pubkey().to_bytes()
+            ], 
+            &setup.program_id
+        );
+
+        investor_redemptions
+    }
+
     async fn redeem_shares(setup: &TestSetup) {
+        redeem_shares_request(setup, 0, 1 * SCALE_FACTOR, vec![]).await.unwrap();
+    }
 
+    async fn redeem_shares_request(
+        setup: &TestSetup,
+        request_id: u64,
+        shares_to_redeem: u64,
+        extra_accounts: Vec<AccountMeta>
+    ) -> Result<(), BanksClientError> {
         let TestSetup {
             program_id,
             punto_xero_master,
@@ -321,39 +480,47 @@ mod tests {
             fund_vault,
             banks_client,
             recent_blockhash,
-            fund_name
+            fund_name,
+            token_program: _,
+            context: _
         } = setup;
 
         let mut banks_client = banks_client.lock().await;
 
-        let buyer_ata = get_associated_token_address(&buyer.pubkey(), &fund_mint);
-
-        let (buyer_share_redemption, _bump_seed) = Pubkey::find_program_address(
-            &[b"share_redemption", fund_name.as_bytes(), &buyer.pubkey().to_bytes()], 
-            &program_id
+        let buyer_ata = get_associated_token_address_with_program_id(
+            &buyer.pubkey(), 
+            &fund_mint, 
+            &setup.token_program
         );
 
+        let buyer_share_redemption = get_share_redemption_address(setup, request_id);
+        let buyer_investor_redemptions = get_investor_redemptions_address(setup);
+
         let share_redemption_payload 
-            = RedeemSharesPayload { shares_to_redeem: 1 * SCALE_FACTOR, fund_name: fund_name.clone() };
+            = RedeemSharesPayload { shares_to_redeem, fund_name: fund_name.clone() };
 
         let mut payload_data = Vec::new();
         share_redemption_payload.serialize(&mut payload_data)
             .unwrap();
 
+        let mut accounts = vec![
+            AccountMeta::new(punto_xero_master.pubkey(), true),
+            AccountMeta::new(*fund_account, false),
+            AccountMeta::new(*fund_mint, false),
+            AccountMeta::new(*fund_vault, false),
+            AccountMeta::new(buyer_share_redemption, false),
+            AccountMeta::new(buyer_investor_redemptions, false),
+            AccountMeta::new(buyer.pubkey(), true),
+            AccountMeta::new(buyer_ata, false),
+            AccountMeta::new_readonly(setup.token_program, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+        accounts.extend(extra_accounts);
+
         let instruction = Instruction::new_with_bytes(
             *program_id, 
             &[&[3][..], &payload_data].concat(), 
-            vec![
-                AccountMeta::new(punto_xero_master.pubkey(), true),
-                AccountMeta::new(*fund_account, false),
-                AccountMeta::new(*fund_mint, false),
-                AccountMeta::new(*fund_vault, false),
-                AccountMeta::new(buyer_share_redemption, false),
-                AccountMeta::new(buyer.pubkey(), true),
-                AccountMeta::new(buyer_ata, false),
-                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
-                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            ]
+            accounts
         );
 
         let transaction = Transaction::new_signed_with_payer(
@@ -366,8 +533,7 @@ mod tests {
             *recent_blockhash
         );
 
-        banks_client.process_transaction(transaction).await.unwrap();
-
+        banks_client.process_transaction(transaction).await
     }
 
     #[tokio::test]
@@ -390,7 +556,9 @@ mod tests {
             fund_vault: _,
             banks_client,
             recent_blockhash: _,
-            fund_name: _
+            fund_name: _,
+            token_program: _,
+            context: _
         } = setup;
 
         let mut banks_client = banks_client.lock().await;
@@ -421,47 +589,86 @@ mod tests {
     }
 
     async fn process_share_redemption(setup: &TestSetup) {
+        process_share_redemption_with_extra_accounts(setup, vec![]).await;
+    }
+
+    async fn process_share_redemption_with_extra_accounts(
+        setup: &TestSetup,
+        extra_accounts: Vec<AccountMeta>
+    ) {
+        process_share_redemption_request(setup, 0, SCALE_FACTOR, 10_000 * SCALE_FACTOR, extra_accounts).await.unwrap();
+    }
+
+    async fn process_share_redemption_request(
+        setup: &TestSetup,
+        request_id: u64,
+        shares_to_process: u64,
+        amount_payed: u64,
+        extra_accounts: Vec<AccountMeta>
+    ) -> Result<(), BanksClientError> {
+        process_share_redemption_signed_by(
+            setup, 
+            &setup.fund_manager_master, 
+            request_id, 
+            shares_to_process, 
+            amount_payed, 
+            extra_accounts
+        ).await
+    }
+
+    async fn process_share_redemption_signed_by(
+        setup: &TestSetup,
+        fund_manager_master: &Keypair,
+        request_id: u64,
+        shares_to_process: u64,
+        amount_payed: u64,
+        extra_accounts: Vec<AccountMeta>
+    ) -> Result<(), BanksClientError> {
         let TestSetup {
             program_id,
             punto_xero_master,
-            fund_manager_master,
+            fund_manager_master: _,
             buyer,
             fund_account,
             fund_mint,
             fund_vault,
             banks_client,
             recent_blockhash,
-            fund_name
+            fund_name,
+            token_program: _,
+            context: _
         } = setup;
 
         let mut banks_client = banks_client.lock().await;
 
-        let (buyer_share_redemption, _bump_seed) = Pubkey::find_program_address(
-            &[b"share_redemption", fund_name.as_bytes(), &buyer.pubkey().to_bytes()], 
-            &program_id
-        );
+        let buyer_share_redemption = get_share_redemption_address(setup, request_id);
+        let buyer_investor_redemptions = get_investor_redemptions_address(setup);
 
         let share_redemption_payload 
-            = ProcessSharesRedemptionPayload { amount_payed: 10_000 * SCALE_FACTOR, fund_name: fund_name.clone() };
+            = ProcessSharesRedemptionPayload { shares_to_process, amount_payed, fund_name: fund_name.clone() };
 
         let mut payload_data = Vec::new();
         share_redemption_payload.serialize(&mut payload_data)
             .unwrap();
 
+        let mut accounts = vec![
+            AccountMeta::new(punto_xero_master.pubkey(), true),
+            AccountMeta::new_readonly(fund_manager_master.pubkey(), true),
+            AccountMeta::new(*fund_account, false),
+            AccountMeta::new(*fund_mint, false),
+            AccountMeta::new(*fund_vault, false),
+            AccountMeta::new(buyer_share_redemption, false),
+            AccountMeta::new(buyer_investor_redemptions, false),
+            AccountMeta::new(buyer.pubkey(), false),
+            AccountMeta::new_readonly(setup.token_program, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ];
+        accounts.extend(extra_accounts);
+
         let instruction = Instruction::new_with_bytes(
             *program_id, 
             &[&[4][..], &payload_data].concat(), 
-            vec![
-                AccountMeta::new(punto_xero_master.pubkey(), true),
-                AccountMeta::new_readonly(fund_manager_master.pubkey(), true),
-                AccountMeta::new(*fund_account, false),
-                AccountMeta::new(*fund_mint, false),
-                AccountMeta::new(*fund_vault, false),
-                AccountMeta::new(buyer_share_redemption, false),
-                AccountMeta::new(buyer.pubkey(), false),
-                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
-                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            ]
+            accounts
         );
 
         let transaction = Transaction::new_signed_with_payer(
@@ -474,7 +681,7 @@ mod tests {
             *recent_blockhash
         );
 
-        banks_client.process_transaction(transaction).await.unwrap();
+        banks_client.process_transaction(transaction).await
     }
 
 
@@ -490,28 +697,27 @@ mod tests {
 
         process_share_redemption(&setup).await;
 
+        let buyer_share_redemption = get_share_redemption_address(&setup, 0);
+
         let TestSetup {
-            program_id,
+            program_id: _,
             punto_xero_master: _,
             fund_manager_master: _,
-            buyer,
+            buyer: _,
             fund_account,
             fund_mint,
             fund_vault: _,
             banks_client,
             recent_blockhash: _,
-            fund_name
+            fund_name: _,
+            token_program: _,
+            context: _
         } = setup;
 
         let mut banks_client = banks_client.lock().await;
 
         let fund_vault = get_associated_token_address(&fund_account, &fund_mint);
 
-        let (buyer_share_redemption, _bump_seed) = Pubkey::find_program_address(
-            &[b"share_redemption", fund_name.as_bytes(), &buyer.pubkey().to_bytes()], 
-            &program_id
-        );
-
         let redemption_account = banks_client
             .get_account(buyer_share_redemption)
             .await
@@ -534,4 +740,3147 @@ mod tests {
 
     }
 
-}
\ No newline at end of file
+    async fn create_settlement_mint(setup: &TestSetup) -> Pubkey {
+        let TestSetup {
+            program_id: _,
+            punto_xero_master,
+            fund_manager_master: _,
+            buyer,
+            fund_account: _,
+            fund_mint: _,
+            fund_vault: _,
+            banks_client,
+            recent_blockhash,
+            fund_name: _,
+            token_program: _,
+            context: _
+        } = setup;
+
+        let mut banks_client = banks_client.lock().await;
+
+        let settlement_mint = Keypair::new();
+        let rent = banks_client.get_rent().await.unwrap();
+
+        let buyer_payment_ata = get_associated_token_address(
+            &buyer.pubkey(), 
+            &settlement_mint.pubkey()
+        );
+
+        let instructions = [
+            create_account(
+                &punto_xero_master.pubkey(), 
+                &settlement_mint.pubkey(), 
+                rent.minimum_balance(Mint::LEN), 
+                Mint::LEN as u64, 
+                &TOKEN_PROGRAM_ID
+            ),
+            initialize_mint(
+                &TOKEN_PROGRAM_ID, 
+                &settlement_mint.pubkey(), 
+                &punto_xero_master.pubkey(), 
+                None, 
+                6
+            ).unwrap(),
+            create_associated_token_account(
+                &punto_xero_master.pubkey(), 
+                &buyer.pubkey(), 
+                &settlement_mint.pubkey(), 
+                &TOKEN_PROGRAM_ID
+            ),
+            mint_to(
+                &TOKEN_PROGRAM_ID, 
+                &settlement_mint.pubkey(), 
+                &buyer_payment_ata, 
+                &punto_xero_master.pubkey(), 
+                &[], 
+                100_000 * SCALE_FACTOR
+            ).unwrap(),
+        ];
+
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions, 
+            Some(&punto_xero_master.pubkey()), 
+            &[
+                punto_xero_master,
+                &settlement_mint
+            ], 
+            *recent_blockhash
+        );
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        settlement_mint.pubkey()
+    }
+
+    #[tokio::test]
+    async fn test_buy_shares_with_settlement_mint() {
+        let setup = get_setup().await;
+
+        let settlement_mint = create_settlement_mint(&setup).await;
+        let cash_vault = get_associated_token_address(&setup.fund_account, &settlement_mint);
+        let buyer_payment_ata = get_associated_token_address(&setup.buyer.pubkey(), &settlement_mint);
+
+        initialize_fund_with_extra_accounts(
+            &setup, 
+            vec![
+                AccountMeta::new_readonly(settlement_mint, false),
+                AccountMeta::new(cash_vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ]
+        ).await;
+
+        buy_shares_with_extra_accounts(
+            &setup, 
+            vec![
+                AccountMeta::new(buyer_payment_ata, false),
+                AccountMeta::new(cash_vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ]
+        ).await.unwrap();
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let fund_account_info = banks_client
+            .get_account(setup.fund_account)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let fund_account_data = try_from_slice_unchecked::<state::fund_account::FundAccount>(&fund_account_info.data)
+            .unwrap();
+
+        let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &setup.fund_mint);
+
+        let buyer_shares = TokenAccount::unpack(
+            &banks_client.get_account(buyer_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let cash_vault_data = TokenAccount::unpack(
+            &banks_client.get_account(cash_vault).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let buyer_payment_data = TokenAccount::unpack(
+            &banks_client.get_account(buyer_payment_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(fund_account_data.settlement_mint, Some(settlement_mint));
+        assert_eq!(fund_account_data.cash_vault, Some(cash_vault));
+        assert_eq!(buyer_shares.amount, 2 * SCALE_FACTOR);
+        assert_eq!(cash_vault_data.amount, 20_000 * SCALE_FACTOR);
+        assert_eq!(buyer_payment_data.amount, 80_000 * SCALE_FACTOR);
+    }
+
+    #[tokio::test]
+    async fn test_process_share_redemption_with_settlement_payout() {
+        let setup = get_setup().await;
+
+        let settlement_mint = create_settlement_mint(&setup).await;
+        let cash_vault = get_associated_token_address(&setup.fund_account, &settlement_mint);
+        let buyer_payment_ata = get_associated_token_address(&setup.buyer.pubkey(), &settlement_mint);
+
+        initialize_fund_with_extra_accounts(
+            &setup, 
+            vec![
+                AccountMeta::new_readonly(settlement_mint, false),
+                AccountMeta::new(cash_vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ]
+        ).await;
+
+        buy_shares_with_extra_accounts(
+            &setup, 
+            vec![
+                AccountMeta::new(buyer_payment_ata, false),
+                AccountMeta::new(cash_vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ]
+        ).await.unwrap();
+
+        redeem_shares(&setup).await;
+
+        let payout_accounts = vec![
+            AccountMeta::new(cash_vault, false),
+            AccountMeta::new(buyer_payment_ata, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ];
+
+        // an investor cannot pay themselves out by signing as the manager
+        let impostor_error = process_share_redemption_signed_by(
+            &setup, 
+            &Keypair::new(), 
+            0, 
+            SCALE_FACTOR, 
+            10_000 * SCALE_FACTOR, 
+            payout_accounts.clone()
+        ).await.unwrap_err().unwrap();
+
+        assert_eq!(
+            impostor_error, 
+            TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature)
+        );
+
+        process_share_redemption_with_extra_accounts(&setup, payout_accounts).await;
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let cash_vault_data = TokenAccount::unpack(
+            &banks_client.get_account(cash_vault).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let buyer_payment_data = TokenAccount::unpack(
+            &banks_client.get_account(buyer_payment_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(cash_vault_data.amount, 10_000 * SCALE_FACTOR);
+        assert_eq!(buyer_payment_data.amount, 90_000 * SCALE_FACTOR);
+    }
+
+    #[tokio::test]
+    async fn test_token_2022_fund_with_classic_settlement_mint() {
+        let setup = get_setup_with_token_program(TOKEN_2022_PROGRAM_ID).await;
+
+        let settlement_mint = create_settlement_mint(&setup).await;
+        let cash_vault = get_associated_token_address(&setup.fund_account, &settlement_mint);
+        let buyer_payment_ata = get_associated_token_address(&setup.buyer.pubkey(), &settlement_mint);
+
+        initialize_fund_with_extra_accounts(
+            &setup, 
+            vec![
+                AccountMeta::new_readonly(settlement_mint, false),
+                AccountMeta::new(cash_vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new(get_extra_account_metas_address(&setup.fund_mint, &setup.program_id), false),
+            ]
+        ).await;
+
+        let wrong_program_error = buy_shares_with_extra_accounts(
+            &setup, 
+            vec![
+                AccountMeta::new(buyer_payment_ata, false),
+                AccountMeta::new(cash_vault, false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            ]
+        ).await.unwrap_err().unwrap();
+
+        assert_eq!(
+            wrong_program_error, 
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        );
+
+        buy_shares_with_extra_accounts(
+            &setup, 
+            vec![
+                AccountMeta::new(buyer_payment_ata, false),
+                AccountMeta::new(cash_vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ]
+        ).await.unwrap();
+
+        redeem_shares(&setup).await;
+
+        process_share_redemption_with_extra_accounts(
+            &setup, 
+            vec![
+                AccountMeta::new(cash_vault, false),
+                AccountMeta::new(buyer_payment_ata, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ]
+        ).await;
+
+        assert_eq!(get_token_2022_balance(&setup, &setup.buyer.pubkey()).await, SCALE_FACTOR);
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let fund_account_data = try_from_slice_unchecked::<state::fund_account::FundAccount>(
+            &banks_client.get_account(setup.fund_account).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let cash_vault_data = TokenAccount::unpack(
+            &banks_client.get_account(cash_vault).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let buyer_payment_data = TokenAccount::unpack(
+            &banks_client.get_account(buyer_payment_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(fund_account_data.settlement_token_program, Some(TOKEN_PROGRAM_ID));
+        assert_eq!(cash_vault_data.amount, 10_000 * SCALE_FACTOR);
+        assert_eq!(buyer_payment_data.amount, 90_000 * SCALE_FACTOR);
+    }
+
+    #[tokio::test]
+    async fn test_multiple_open_redemptions() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        buy_shares(&setup).await;
+
+        redeem_shares_request(&setup, 0, SCALE_FACTOR / 2, vec![]).await.unwrap();
+        redeem_shares_request(&setup, 1, SCALE_FACTOR / 4, vec![]).await.unwrap();
+
+        let investor_redemptions_address = get_investor_redemptions_address(&setup);
+
+        {
+            let mut banks_client = setup.banks_client.lock().await;
+
+            let investor_redemptions_info = banks_client
+                .get_account(investor_redemptions_address)
+                .await
+                .unwrap()
+                .unwrap();
+
+            let investor_redemptions = try_from_slice_unchecked::<state::investor_redemptions::InvestorRedemptions>(
+                &investor_redemptions_info.data
+            ).unwrap();
+
+            assert_eq!(investor_redemptions.next_request_id, 2);
+            assert_eq!(investor_redemptions.open_request_ids, vec![0, 1]);
+        }
+
+        process_share_redemption_request(&setup, 0, SCALE_FACTOR / 2, 5_000 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let investor_redemptions_info = banks_client
+            .get_account(investor_redemptions_address)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let investor_redemptions = try_from_slice_unchecked::<state::investor_redemptions::InvestorRedemptions>(
+            &investor_redemptions_info.data
+        ).unwrap();
+
+        let open_redemption_info = banks_client
+            .get_account(get_share_redemption_address(&setup, 1))
+            .await
+            .unwrap()
+            .unwrap();
+
+        let open_redemption = try_from_slice_unchecked::<state::share_redemption::ShareRedemption>(
+            &open_redemption_info.data
+        ).unwrap();
+
+        assert_eq!(investor_redemptions.open_request_ids, vec![1]);
+        assert_eq!(open_redemption.request_id, 1);
+        assert_eq!(open_redemption.shares_amount, SCALE_FACTOR / 4);
+    }
+
+    async fn advance_clock(setup: &TestSetup, seconds: i64) {
+        let mut context = setup.context.lock().await;
+
+        let mut clock = context.banks_client.get_sysvar::<Clock>()
+            .await
+            .unwrap();
+
+        clock.unix_timestamp += seconds;
+        context.set_sysvar(&clock);
+    }
+
+    async fn set_clock(setup: &TestSetup, unix_timestamp: i64) {
+        let mut context = setup.context.lock().await;
+
+        let mut clock = context.banks_client.get_sysvar::<Clock>()
+            .await
+            .unwrap();
+
+        clock.unix_timestamp = unix_timestamp;
+        context.set_sysvar(&clock);
+    }
+
+    async fn update_fund_config(setup: &TestSetup, config: FundConfig) {
+        update_fund_config_request(setup, config, &[]).await.unwrap();
+    }
+
+    async fn update_fund_config_request(
+        setup: &TestSetup, 
+        config: FundConfig,
+        approvers: &[&Keypair]
+    ) -> Result<(), BanksClientError> {
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let update_fund_config_payload 
+            = UpdateFundConfigPayload { config, fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        update_fund_config_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let mut accounts = vec![
+            AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+            AccountMeta::new_readonly(setup.fund_manager_master.pubkey(), true),
+            AccountMeta::new(setup.fund_account, false),
+            AccountMeta::new_readonly(setup.fund_mint, false),
+        ];
+        accounts.extend(approvers.iter().map(|approver| AccountMeta::new_readonly(approver.pubkey(), true)));
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[5][..], &payload_data].concat(),
+            accounts
+        );
+
+        let mut signers = vec![&setup.punto_xero_master, &setup.fund_manager_master];
+        signers.extend(approvers);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &signers, 
+            setup.recent_blockhash
+        );
+
+        banks_client.process_transaction(transaction).await
+    }
+
+    async fn cancel_redemption(
+        setup: &TestSetup, 
+        request_id: u64
+    ) -> Result<(), BanksClientError> {
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let buyer_ata = get_associated_token_address_with_program_id(
+            &setup.buyer.pubkey(), 
+            &setup.fund_mint, 
+            &setup.token_program
+        );
+
+        let cancel_redemption_payload 
+            = CancelRedemptionPayload { fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        cancel_redemption_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[6][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+                AccountMeta::new(setup.fund_account, false),
+                AccountMeta::new(setup.fund_mint, false),
+                AccountMeta::new(setup.fund_vault, false),
+                AccountMeta::new(get_share_redemption_address(setup, request_id), false),
+                AccountMeta::new(get_investor_redemptions_address(setup), false),
+                AccountMeta::new_readonly(setup.buyer.pubkey(), true),
+                AccountMeta::new(buyer_ata, false),
+                AccountMeta::new_readonly(setup.token_program, false),
+            ]
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                &setup.buyer
+            ], 
+            setup.recent_blockhash
+        );
+
+        banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_cancel_redemption() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        buy_shares(&setup).await;
+
+        redeem_shares(&setup).await;
+
+        cancel_redemption(&setup, 0).await.unwrap();
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &setup.fund_mint);
+
+        let buyer_token_account_data = TokenAccount::unpack(
+            &banks_client.get_account(buyer_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let vault_token_account_data = TokenAccount::unpack(
+            &banks_client.get_account(setup.fund_vault).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let redemption_account = banks_client
+            .get_account(get_share_redemption_address(&setup, 0))
+            .await
+            .unwrap()
+            .unwrap();
+
+        let investor_redemptions = try_from_slice_unchecked::<state::investor_redemptions::InvestorRedemptions>(
+            &banks_client.get_account(get_investor_redemptions_address(&setup)).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(buyer_token_account_data.amount, 2 * SCALE_FACTOR);
+        assert_eq!(vault_token_account_data.amount, 0);
+        assert_eq!(redemption_account.owner, SYSTEM_PROGRAM_ID);
+        assert!(investor_redemptions.open_request_ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_redemption_after_cutoff() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                cancellation_cutoff_seconds: 3_600, 
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        buy_shares(&setup).await;
+
+        redeem_shares(&setup).await;
+
+        advance_clock(&setup, 7_200).await;
+
+        let error = cancel_redemption(&setup, 0).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::CancellationWindowClosed as u32)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_share_redemption() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        buy_shares(&setup).await;
+
+        redeem_shares(&setup).await;
+
+        process_share_redemption_request(&setup, 0, SCALE_FACTOR / 4, 2_500 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        {
+            let mut banks_client = setup.banks_client.lock().await;
+
+            let share_redemption = try_from_slice_unchecked::<state::share_redemption::ShareRedemption>(
+                &banks_client.get_account(get_share_redemption_address(&setup, 0)).await.unwrap().unwrap().data
+            ).unwrap();
+
+            let vault_token_account_data = TokenAccount::unpack(
+                &banks_client.get_account(setup.fund_vault).await.unwrap().unwrap().data
+            ).unwrap();
+
+            assert_eq!(share_redemption.shares_amount, SCALE_FACTOR * 3 / 4);
+            assert_eq!(share_redemption.processed_shares, SCALE_FACTOR / 4);
+            assert_eq!(share_redemption.amount_payed, 2_500 * SCALE_FACTOR);
+            assert_eq!(share_redemption.fill_count, 1);
+            assert_eq!(vault_token_account_data.amount, SCALE_FACTOR * 3 / 4);
+        }
+
+        process_share_redemption_request(&setup, 0, SCALE_FACTOR * 3 / 4, 7_500 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let redemption_account = banks_client
+            .get_account(get_share_redemption_address(&setup, 0))
+            .await
+            .unwrap()
+            .unwrap();
+
+        let investor_redemptions = try_from_slice_unchecked::<state::investor_redemptions::InvestorRedemptions>(
+            &banks_client.get_account(get_investor_redemptions_address(&setup)).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(redemption_account.owner, SYSTEM_PROGRAM_ID);
+        assert!(investor_redemptions.open_request_ids.is_empty());
+    }
+
+    async fn accrue_fees(setup: &TestSetup) {
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let manager_fee_ata = get_associated_token_address(
+            &setup.fund_manager_master.pubkey(), 
+            &setup.fund_mint
+        );
+
+        let platform_fee_ata = get_associated_token_address(
+            &setup.punto_xero_master.pubkey(), 
+            &setup.fund_mint
+        );
+
+        let accrue_fees_payload 
+            = AccrueFeesPayload { fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        accrue_fees_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[7][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+                AccountMeta::new(setup.fund_account, false),
+                AccountMeta::new(setup.fund_mint, false),
+                AccountMeta::new_readonly(setup.fund_manager_master.pubkey(), false),
+                AccountMeta::new(manager_fee_ata, false),
+                AccountMeta::new_readonly(setup.punto_xero_master.pubkey(), false),
+                AccountMeta::new(platform_fee_ata, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, false),
+            ]
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[&setup.punto_xero_master], 
+            setup.recent_blockhash
+        );
+
+        banks_client.process_transaction(transaction).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_management_fee_accrual() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                management_fee_bps: 200, 
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        buy_shares(&setup).await;
+
+        advance_clock(&setup, 31_536_000).await;
+
+        accrue_fees(&setup).await;
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let manager_fee_ata = get_associated_token_address(
+            &setup.fund_manager_master.pubkey(), 
+            &setup.fund_mint
+        );
+
+        let manager_fee_account_data = TokenAccount::unpack(
+            &banks_client.get_account(manager_fee_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let fund_account_data = try_from_slice_unchecked::<state::fund_account::FundAccount>(
+            &banks_client.get_account(setup.fund_account).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(manager_fee_account_data.amount, 2 * SCALE_FACTOR * 200 / 10_000);
+        assert_eq!(fund_account_data.accrued_management_fee_shares, 0);
+    }
+
+    async fn update_share_value(setup: &TestSetup, new_share_value: u64) {
+        update_share_value_with_extra_accounts(setup, new_share_value, vec![]).await;
+    }
+
+    async fn update_share_value_with_extra_accounts(
+        setup: &TestSetup, 
+        new_share_value: u64,
+        extra_accounts: Vec<AccountMeta>
+    ) {
+        update_share_value_request(setup, new_share_value, extra_accounts).await.unwrap();
+    }
+
+    async fn update_share_value_request(
+        setup: &TestSetup, 
+        new_share_value: u64,
+        extra_accounts: Vec<AccountMeta>
+    ) -> Result<(), BanksClientError> {
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let update_share_value_payload 
+            = UpdateShareValuePayload { new_share_value, fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        update_share_value_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let mut accounts = vec![
+            AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+            AccountMeta::new_readonly(setup.fund_manager_master.pubkey(), true),
+            AccountMeta::new(setup.fund_account, false),
+            AccountMeta::new_readonly(setup.fund_mint, false),
+        ];
+        accounts.extend(extra_accounts);
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[1][..], &payload_data].concat(),
+            accounts
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                &setup.fund_manager_master
+            ], 
+            setup.recent_blockhash
+        );
+
+        banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_performance_fee_high_water_mark() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                performance_fee_bps: 2_000, 
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        buy_shares(&setup).await;
+
+        update_share_value(&setup, 11_000 * SCALE_FACTOR).await;
+        update_share_value(&setup, 10_500 * SCALE_FACTOR).await;
+
+        accrue_fees(&setup).await;
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let manager_fee_ata = get_associated_token_address(
+            &setup.fund_manager_master.pubkey(), 
+            &setup.fund_mint
+        );
+
+        let manager_fee_account_data = TokenAccount::unpack(
+            &banks_client.get_account(manager_fee_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let fund_account_data = try_from_slice_unchecked::<state::fund_account::FundAccount>(
+            &banks_client.get_account(setup.fund_account).await.unwrap().unwrap().data
+        ).unwrap();
+
+        // 20% of the 1_000 gain on 2 shares, paid in shares at the 11_000 mark
+        assert_eq!(manager_fee_account_data.amount, 36_363);
+        assert_eq!(fund_account_data.high_water_mark, 11_000 * SCALE_FACTOR);
+        assert_eq!(fund_account_data.accrued_performance_fee_shares, 0);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_and_redemption_fees() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                subscription_fee_bps: 100, 
+                redemption_fee_bps: 100, 
+                platform_fee_share_bps: 5_000, 
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        buy_shares(&setup).await;
+
+        redeem_shares(&setup).await;
+
+        process_share_redemption_request(&setup, 0, SCALE_FACTOR, 9_900 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        accrue_fees(&setup).await;
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &setup.fund_mint);
+
+        let manager_fee_ata = get_associated_token_address(
+            &setup.fund_manager_master.pubkey(), 
+            &setup.fund_mint
+        );
+
+        let platform_fee_ata = get_associated_token_address(
+            &setup.punto_xero_master.pubkey(), 
+            &setup.fund_mint
+        );
+
+        let buyer_token_account_data = TokenAccount::unpack(
+            &banks_client.get_account(buyer_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let manager_fee_account_data = TokenAccount::unpack(
+            &banks_client.get_account(manager_fee_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let platform_fee_account_data = TokenAccount::unpack(
+            &banks_client.get_account(platform_fee_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(buyer_token_account_data.amount, 2 * SCALE_FACTOR * 99 / 100 - SCALE_FACTOR);
+        assert_eq!(manager_fee_account_data.amount, 15_000);
+        assert_eq!(platform_fee_account_data.amount, 15_000);
+    }
+
+    #[tokio::test]
+    async fn test_fee_basis_points_capped() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        let invalid_configs = [
+            FundConfig { subscription_fee_bps: 20_000, ..FundConfig::default() },
+            FundConfig { redemption_fee_bps: 10_001, ..FundConfig::default() },
+            FundConfig { platform_fee_share_bps: 10_001, ..FundConfig::default() },
+            FundConfig { early_redemption_fee_bps: 10_001, ..FundConfig::default() },
+        ];
+
+        for config in invalid_configs {
+            let fee_error = update_fund_config_request(&setup, config, &[]).await
+                .unwrap_err()
+                .unwrap();
+
+            assert_eq!(
+                fee_error, 
+                TransactionError::InstructionError(
+                    0, 
+                    InstructionError::Custom(CustomError::InvalidFeeBasisPoints as u32)
+                )
+            );
+        }
+
+        update_fund_config(
+            &setup, 
+            FundConfig { subscription_fee_bps: 10_000, ..FundConfig::default() }
+        ).await;
+    }
+
+    fn get_nav_history_address(setup: &TestSetup) -> Pubkey {
+        let (nav_history, _bump_seed) = Pubkey::find_program_address(
+            &[b"nav_history", &setup.fund_account.to_bytes()], 
+            &setup.program_id
+        );
+
+        nav_history
+    }
+
+    async fn init_nav_history(setup: &TestSetup) {
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let init_nav_history_payload 
+            = InitNavHistoryPayload { fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        init_nav_history_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[8][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+                AccountMeta::new_readonly(setup.fund_manager_master.pubkey(), true),
+                AccountMeta::new(setup.fund_account, false),
+                AccountMeta::new(get_nav_history_address(setup), false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ]
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                &setup.fund_manager_master
+            ], 
+            setup.recent_blockhash
+        );
+
+        banks_client.process_transaction(transaction).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_nav_history() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        init_nav_history(&setup).await;
+
+        let nav_history_address = get_nav_history_address(&setup);
+
+        update_share_value_with_extra_accounts(
+            &setup, 
+            11_000 * SCALE_FACTOR, 
+            vec![AccountMeta::new(nav_history_address, false)]
+        ).await;
+
+        update_share_value_with_extra_accounts(
+            &setup, 
+            12_000 * SCALE_FACTOR, 
+            vec![AccountMeta::new(nav_history_address, false)]
+        ).await;
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let nav_history = try_from_slice_unchecked::<state::nav_history::NavHistory>(
+            &banks_client.get_account(nav_history_address).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let share_values = nav_history.latest(3)
+            .iter()
+            .map(|entry| entry.share_value)
+            .collect::<Vec<u64>>();
+
+        assert_eq!(
+            share_values, 
+            vec![12_000 * SCALE_FACTOR, 11_000 * SCALE_FACTOR, 10_000 * SCALE_FACTOR]
+        );
+    }
+
+    #[test]
+    fn test_nav_history_wraps_around() {
+        let mut nav_history = state::nav_history::NavHistory {
+            is_initialized: true,
+            bump_seed: 0,
+            fund_account: Pubkey::new_unique(),
+            head: 0,
+            entries: Vec::new(),
+        };
+
+        let capacity = state::nav_history::NavHistory::CAPACITY;
+
+        for share_value in 0..(capacity as u64 + 3) {
+            nav_history.push(state::nav_history::NavEntry { 
+                timestamp: share_value as i64, 
+                share_value 
+            });
+        }
+
+        assert_eq!(nav_history.entries.len(), capacity);
+        assert_eq!(nav_history.head, 3);
+        assert_eq!(nav_history.latest(1)[0].share_value, capacity as u64 + 2);
+        assert_eq!(nav_history.latest(capacity).last().unwrap().share_value, 3);
+    }
+
+    #[tokio::test]
+    async fn test_stale_share_value_rejected() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                max_nav_age_seconds: 86_400, 
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        let stale_share_value_error = TransactionError::InstructionError(
+            0, 
+            InstructionError::Custom(CustomError::StaleShareValue as u32)
+        );
+
+        advance_clock(&setup, 2 * 86_400).await;
+
+        let buy_error = buy_shares_request(&setup, 20_000 * SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(buy_error, stale_share_value_error);
+
+        update_share_value(&setup, 10_000 * SCALE_FACTOR).await;
+
+        buy_shares_request(&setup, 30_000 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        advance_clock(&setup, 2 * 86_400).await;
+
+        let redeem_error = redeem_shares_request(&setup, 0, SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(redeem_error, stale_share_value_error);
+
+        update_share_value(&setup, 10_500 * SCALE_FACTOR).await;
+
+        redeem_shares_request(&setup, 0, 2 * SCALE_FACTOR, vec![]).await.unwrap();
+    }
+
+    async fn override_share_value_request(
+        setup: &TestSetup, 
+        risk_officer: &Keypair,
+        new_share_value: u64
+    ) -> Result<(), BanksClientError> {
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let override_share_value_payload 
+            = UpdateShareValuePayload { new_share_value, fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        override_share_value_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[9][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+                AccountMeta::new_readonly(setup.fund_manager_master.pubkey(), true),
+                AccountMeta::new_readonly(risk_officer.pubkey(), true),
+                AccountMeta::new(setup.fund_account, false),
+                AccountMeta::new_readonly(setup.fund_mint, false),
+            ]
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                &setup.fund_manager_master,
+                risk_officer
+            ], 
+            setup.recent_blockhash
+        );
+
+        banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_share_value_change_band() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        let risk_officer = Keypair::new();
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                max_share_value_change_bps: 1_000, 
+                risk_officer: risk_officer.pubkey(),
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        let zero_error = update_share_value_request(&setup, 0, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            zero_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::InvalidShareValue as u32)
+            )
+        );
+
+        let band_error = update_share_value_request(&setup, 12_000 * SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            band_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::ShareValueChangeTooLarge as u32)
+            )
+        );
+
+        update_share_value(&setup, 11_000 * SCALE_FACTOR).await;
+
+        override_share_value_request(&setup, &Keypair::new(), 14_000 * SCALE_FACTOR).await
+            .unwrap_err();
+
+        override_share_value_request(&setup, &risk_officer, 13_000 * SCALE_FACTOR).await
+            .unwrap();
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let fund_account_data = try_from_slice_unchecked::<state::fund_account::FundAccount>(
+            &banks_client.get_account(setup.fund_account).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(fund_account_data.share_value, 13_000 * SCALE_FACTOR);
+    }
+
+    #[tokio::test]
+    async fn test_risk_officer_config_approval() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        let risk_officer = Keypair::new();
+
+        let banded_config = FundConfig { 
+            max_share_value_change_bps: 1_000, 
+            risk_officer: risk_officer.pubkey(),
+            ..FundConfig::default() 
+        };
+
+        update_fund_config(&setup, banded_config.clone()).await;
+
+        let approval_error = TransactionError::InstructionError(
+            0, 
+            InstructionError::Custom(CustomError::RiskOfficerApprovalRequired as u32)
+        );
+
+        let widen_error = update_fund_config_request(
+            &setup, 
+            FundConfig { max_share_value_change_bps: 5_000, ..banded_config.clone() },
+            &[]
+        ).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(widen_error, approval_error);
+
+        let impostor = Keypair::new();
+
+        let replace_error = update_fund_config_request(
+            &setup, 
+            FundConfig { risk_officer: impostor.pubkey(), ..banded_config.clone() },
+            &[&impostor]
+        ).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(replace_error, approval_error);
+
+        // other settings stay with the manager
+        update_fund_config(&setup, FundConfig { lock_up_seconds: 60, ..banded_config.clone() }).await;
+
+        update_fund_config_request(
+            &setup, 
+            FundConfig { max_share_value_change_bps: 2_000, ..banded_config },
+            &[&risk_officer]
+        ).await
+            .unwrap();
+
+        update_share_value(&setup, 11_500 * SCALE_FACTOR).await;
+    }
+
+    async fn set_oracle_price_feed(
+        setup: &TestSetup, 
+        oracle: &Pubkey, 
+        oracle_program: &Pubkey, 
+        price_feed: PriceFeed
+    ) {
+        let mut context = setup.context.lock().await;
+
+        let mut data = Vec::new();
+        price_feed.serialize(&mut data)
+            .unwrap();
+
+        let account = Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: *oracle_program,
+            executable: false,
+            rent_epoch: 0
+        };
+
+        context.set_account(oracle, &AccountSharedData::from(account));
+    }
+
+    async fn refresh_share_value_from_oracle_request(
+        setup: &TestSetup, 
+        oracle: &Pubkey
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let refresh_payload 
+            = RefreshShareValueFromOraclePayload { fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        refresh_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[10][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new(setup.fund_account, false),
+                AccountMeta::new_readonly(setup.fund_mint, false),
+                AccountMeta::new_readonly(*oracle, false),
+            ]
+        );
+
+        // every refresh carries the same instruction, so fetch a fresh blockhash
+        // to keep the banks client from answering with a cached result
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[&setup.punto_xero_master], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_refresh_share_value_from_oracle() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        let oracle = Pubkey::new_unique();
+        let oracle_program = Pubkey::new_unique();
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                oracle,
+                oracle_program,
+                max_oracle_confidence_bps: 100,
+                max_oracle_age_seconds: 60,
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        let manager_update_error = update_share_value_request(&setup, 10_500 * SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            manager_update_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::ShareValueSourcedFromOracle as u32)
+            )
+        );
+
+        advance_clock(&setup, 10).await;
+
+        let now = setup.banks_client.lock().await
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp;
+
+        set_oracle_price_feed(
+            &setup, 
+            &oracle, 
+            &oracle_program, 
+            PriceFeed { price: 10_500 * SCALE_FACTOR, confidence: 50 * SCALE_FACTOR, publish_timestamp: now }
+        ).await;
+
+        refresh_share_value_from_oracle_request(&setup, &oracle).await.unwrap();
+
+        let fund_account_data = try_from_slice_unchecked::<state::fund_account::FundAccount>(
+            &setup.banks_client.lock().await.get_account(setup.fund_account).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(fund_account_data.share_value, 10_500 * SCALE_FACTOR);
+        assert_eq!(fund_account_data.share_value_update, now);
+
+        // refreshing an unchanged feed later must not make the nav look fresher
+        advance_clock(&setup, 30).await;
+
+        refresh_share_value_from_oracle_request(&setup, &oracle).await.unwrap();
+
+        let fund_account_data = try_from_slice_unchecked::<state::fund_account::FundAccount>(
+            &setup.banks_client.lock().await.get_account(setup.fund_account).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(fund_account_data.share_value_update, now);
+
+        set_oracle_price_feed(
+            &setup, 
+            &oracle, 
+            &oracle_program, 
+            PriceFeed { price: 10_600 * SCALE_FACTOR, confidence: 200 * SCALE_FACTOR, publish_timestamp: now }
+        ).await;
+
+        let confidence_error = refresh_share_value_from_oracle_request(&setup, &oracle).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            confidence_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::OracleConfidenceTooWide as u32)
+            )
+        );
+
+        set_oracle_price_feed(
+            &setup, 
+            &oracle, 
+            &oracle_program, 
+            PriceFeed { price: 10_600 * SCALE_FACTOR, confidence: 50 * SCALE_FACTOR, publish_timestamp: now - 120 }
+        ).await;
+
+        let stale_error = refresh_share_value_from_oracle_request(&setup, &oracle).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            stale_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::StaleOraclePrice as u32)
+            )
+        );
+
+        set_oracle_price_feed(
+            &setup, 
+            &oracle, 
+            &Pubkey::new_unique(), 
+            PriceFeed { price: 10_600 * SCALE_FACTOR, confidence: 50 * SCALE_FACTOR, publish_timestamp: now }
+        ).await;
+
+        let owner_error = refresh_share_value_from_oracle_request(&setup, &oracle).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            owner_error, 
+            TransactionError::InstructionError(0, InstructionError::IllegalOwner)
+        );
+    }
+
+    fn get_pending_nav_address(setup: &TestSetup) -> Pubkey {
+        let (pending_nav, _bump_seed) = Pubkey::find_program_address(
+            &[b"pending_nav", &setup.fund_account.to_bytes()], 
+            &setup.program_id
+        );
+
+        pending_nav
+    }
+
+    async fn propose_share_value_request(
+        setup: &TestSetup, 
+        proposed_share_value: u64
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let propose_share_value_payload = UpdateShareValuePayload { 
+            new_share_value: proposed_share_value, 
+            fund_name: setup.fund_name.clone() 
+        };
+
+        let mut payload_data = Vec::new();
+        propose_share_value_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[11][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+                AccountMeta::new_readonly(setup.fund_manager_master.pubkey(), true),
+                AccountMeta::new_readonly(setup.fund_account, false),
+                AccountMeta::new(get_pending_nav_address(setup), false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ]
+        );
+
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                &setup.fund_manager_master
+            ], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    async fn approve_share_value_request(
+        setup: &TestSetup, 
+        attester: &Keypair
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let approve_share_value_payload 
+            = ApproveShareValuePayload { fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        approve_share_value_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[12][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new_readonly(attester.pubkey(), true),
+                AccountMeta::new(setup.fund_account, false),
+                AccountMeta::new_readonly(setup.fund_mint, false),
+                AccountMeta::new(get_pending_nav_address(setup), false),
+            ]
+        );
+
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                attester
+            ], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    async fn get_share_value(setup: &TestSetup) -> u64 {
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let fund_account_data = try_from_slice_unchecked::<state::fund_account::FundAccount>(
+            &banks_client.get_account(setup.fund_account).await.unwrap().unwrap().data
+        ).unwrap();
+
+        fund_account_data.share_value
+    }
+
+    #[tokio::test]
+    async fn test_share_value_attestation() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        let administrator = Keypair::new();
+        let auditor = Keypair::new();
+        let depositary = Keypair::new();
+        let risk_officer = Keypair::new();
+
+        let attested_config = FundConfig { 
+            nav_attesters: vec![administrator.pubkey(), auditor.pubkey(), depositary.pubkey()],
+            nav_attestation_threshold: 2,
+            nav_attestation_window_seconds: 3_600,
+            risk_officer: risk_officer.pubkey(),
+            ..FundConfig::default() 
+        };
+
+        update_fund_config(&setup, attested_config.clone()).await;
+
+        let custom_error = |error: CustomError| TransactionError::InstructionError(
+            0, 
+            InstructionError::Custom(error as u32)
+        );
+
+        let manager_update_error = update_share_value_request(&setup, 10_500 * SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(manager_update_error, custom_error(CustomError::ShareValueRequiresAttestation));
+
+        let override_error = override_share_value_request(&setup, &risk_officer, 10_500 * SCALE_FACTOR).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(override_error, custom_error(CustomError::ShareValueRequiresAttestation));
+
+        propose_share_value_request(&setup, 10_500 * SCALE_FACTOR).await.unwrap();
+
+        approve_share_value_request(&setup, &administrator).await.unwrap();
+
+        assert_eq!(get_share_value(&setup).await, 10_000 * SCALE_FACTOR);
+
+        let duplicate_error = approve_share_value_request(&setup, &administrator).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(duplicate_error, custom_error(CustomError::DuplicateNavAttestation));
+
+        let outsider_error = approve_share_value_request(&setup, &Keypair::new()).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(outsider_error, custom_error(CustomError::NotANavAttester));
+
+        approve_share_value_request(&setup, &auditor).await.unwrap();
+
+        assert_eq!(get_share_value(&setup).await, 10_500 * SCALE_FACTOR);
+
+        let closed_error = approve_share_value_request(&setup, &depositary).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(closed_error, custom_error(CustomError::NoPendingShareValue));
+
+        propose_share_value_request(&setup, 11_000 * SCALE_FACTOR).await.unwrap();
+
+        advance_clock(&setup, 7_200).await;
+
+        let expired_error = approve_share_value_request(&setup, &administrator).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(expired_error, custom_error(CustomError::NavProposalExpired));
+        assert_eq!(get_share_value(&setup).await, 10_500 * SCALE_FACTOR);
+
+        // switching attestation off needs a quorum of the current attesters
+        let unattested_config = FundConfig { 
+            nav_attesters: vec![],
+            nav_attestation_threshold: 0,
+            ..attested_config 
+        };
+
+        let manager_only_error = update_fund_config_request(&setup, unattested_config.clone(), &[]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(manager_only_error, custom_error(CustomError::NavAttesterApprovalRequired));
+
+        let single_attester_error = update_fund_config_request(
+            &setup, 
+            unattested_config.clone(), 
+            &[&administrator]
+        ).await.unwrap_err().unwrap();
+
+        assert_eq!(single_attester_error, custom_error(CustomError::NavAttesterApprovalRequired));
+
+        update_fund_config_request(&setup, unattested_config, &[&administrator, &depositary]).await
+            .unwrap();
+
+        update_share_value(&setup, 10_600 * SCALE_FACTOR).await;
+
+        assert_eq!(get_share_value(&setup).await, 10_600 * SCALE_FACTOR);
+    }
+
+    fn get_subscription_request_address(setup: &TestSetup, request_id: u64) -> Pubkey {
+        let (subscription_request, _bump_seed) = Pubkey::find_program_address(
+            &[
+                b"subscription_request", 
+                setup.fund_name.as_bytes(), 
+                &request_id.to_le_bytes()
+            ], 
+            &setup.program_id
+        );
+
+        subscription_request
+    }
+
+    async fn request_subscription_request(
+        setup: &TestSetup, 
+        request_id: u64,
+        amount_in_fiat: u64,
+        extra_accounts: Vec<AccountMeta>
+    ) -> Result<(), BanksClientError> {
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let request_subscription_payload 
+            = BuyFundSharesPayload { amount_in_fiat, fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        request_subscription_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let mut accounts = vec![
+            AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+            AccountMeta::new(setup.fund_account, false),
+            AccountMeta::new(get_subscription_request_address(setup, request_id), false),
+            AccountMeta::new_readonly(setup.buyer.pubkey(), true),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ];
+        accounts.extend(extra_accounts);
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[13][..], &payload_data].concat(),
+            accounts
+        );
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                &setup.buyer
+            ], 
+            setup.recent_blockhash
+        );
+
+        banks_client.process_transaction(transaction).await
+    }
+
+    async fn process_subscription_request(
+        setup: &TestSetup, 
+        request_id: u64,
+        extra_accounts: Vec<AccountMeta>
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let process_subscription_payload 
+            = ProcessSubscriptionPayload { fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        process_subscription_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let mut accounts = vec![
+            AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+            AccountMeta::new_readonly(setup.fund_manager_master.pubkey(), true),
+            AccountMeta::new(setup.fund_account, false),
+            AccountMeta::new(setup.fund_mint, false),
+            AccountMeta::new(get_subscription_request_address(setup, request_id), false),
+            AccountMeta::new_readonly(setup.buyer.pubkey(), false),
+            AccountMeta::new(
+                get_associated_token_address(&setup.buyer.pubkey(), &setup.fund_mint), 
+                false
+            ),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_ACCOUNT_PROGRAM_ID, false),
+        ];
+        accounts.extend(extra_accounts);
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[14][..], &payload_data].concat(),
+            accounts
+        );
+
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                &setup.fund_manager_master
+            ], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_forward_priced_subscription() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        init_nav_history(&setup).await;
+
+        let nav_history = vec![AccountMeta::new(get_nav_history_address(&setup), false)];
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                subscription_pricing: PricingMode::Forward, 
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        let buy_error = buy_shares_request(&setup, 20_000 * SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            buy_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::ForwardPricingRequired as u32)
+            )
+        );
+
+        request_subscription_request(&setup, 0, 20_000 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        let early_error = process_subscription_request(&setup, 0, nav_history.clone()).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            early_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::AwaitingNextShareValue as u32)
+            )
+        );
+
+        advance_clock(&setup, 60).await;
+
+        update_share_value_with_extra_accounts(&setup, 12_500 * SCALE_FACTOR, nav_history.clone()).await;
+
+        // a later share value does not reprice the request
+        advance_clock(&setup, 60).await;
+
+        update_share_value_with_extra_accounts(&setup, 13_500 * SCALE_FACTOR, nav_history.clone()).await;
+
+        process_subscription_request(&setup, 0, nav_history).await.unwrap();
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &setup.fund_mint);
+
+        let buyer_token_account_data = TokenAccount::unpack(
+            &banks_client.get_account(buyer_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(buyer_token_account_data.amount, 1_600_000);
+
+        let subscription_request_account = banks_client
+            .get_account(get_subscription_request_address(&setup, 0))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(subscription_request_account.owner, SYSTEM_PROGRAM_ID);
+        assert!(subscription_request_account.data.is_empty());
+    }
+
+    async fn cancel_subscription_request(
+        setup: &TestSetup, 
+        request_id: u64,
+        extra_accounts: Vec<AccountMeta>
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let cancel_subscription_payload 
+            = CancelSubscriptionPayload { fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        cancel_subscription_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let mut accounts = vec![
+            AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+            AccountMeta::new_readonly(setup.fund_account, false),
+            AccountMeta::new(get_subscription_request_address(setup, request_id), false),
+            AccountMeta::new_readonly(setup.buyer.pubkey(), true),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ];
+        accounts.extend(extra_accounts);
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[22][..], &payload_data].concat(),
+            accounts
+        );
+
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                &setup.buyer
+            ], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    async fn reject_subscription_request(
+        setup: &TestSetup, 
+        manager: &Keypair,
+        request_id: u64,
+        extra_accounts: Vec<AccountMeta>
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let reject_subscription_payload 
+            = RejectSubscriptionPayload { fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        reject_subscription_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let mut accounts = vec![
+            AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+            AccountMeta::new_readonly(manager.pubkey(), true),
+            AccountMeta::new_readonly(setup.fund_account, false),
+            AccountMeta::new(get_subscription_request_address(setup, request_id), false),
+            AccountMeta::new_readonly(setup.buyer.pubkey(), false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ];
+        accounts.extend(extra_accounts);
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[23][..], &payload_data].concat(),
+            accounts
+        );
+
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                manager
+            ], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_reject_subscription_refunds_escrow() {
+        let setup = get_setup().await;
+
+        let settlement_mint = create_settlement_mint(&setup).await;
+        let cash_vault = get_associated_token_address(&setup.fund_account, &settlement_mint);
+        let buyer_payment_ata = get_associated_token_address(&setup.buyer.pubkey(), &settlement_mint);
+
+        initialize_fund_with_extra_accounts(
+            &setup, 
+            vec![
+                AccountMeta::new_readonly(settlement_mint, false),
+                AccountMeta::new(cash_vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ]
+        ).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                subscription_pricing: PricingMode::Forward, 
+                cancellation_cutoff_seconds: 60,
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        let payment_accounts = vec![
+            AccountMeta::new(buyer_payment_ata, false),
+            AccountMeta::new(cash_vault, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ];
+
+        request_subscription_request(&setup, 0, 20_000 * SCALE_FACTOR, payment_accounts.clone()).await
+            .unwrap();
+
+        advance_clock(&setup, 120).await;
+
+        // without a settlement deadline the investor is locked in past the cutoff
+        cancel_subscription_request(&setup, 0, payment_accounts.clone()).await
+            .unwrap_err();
+
+        reject_subscription_request(&setup, &Keypair::new(), 0, payment_accounts.clone()).await
+            .unwrap_err();
+
+        reject_subscription_request(&setup, &setup.fund_manager_master, 0, payment_accounts).await
+            .unwrap();
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let cash_vault_data = TokenAccount::unpack(
+            &banks_client.get_account(cash_vault).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let buyer_payment_data = TokenAccount::unpack(
+            &banks_client.get_account(buyer_payment_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let subscription_request_account = banks_client
+            .get_account(get_subscription_request_address(&setup, 0))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(cash_vault_data.amount, 0);
+        assert_eq!(buyer_payment_data.amount, 100_000 * SCALE_FACTOR);
+        assert_eq!(subscription_request_account.owner, SYSTEM_PROGRAM_ID);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_subscription_refunds_escrow() {
+        let setup = get_setup().await;
+
+        let settlement_mint = create_settlement_mint(&setup).await;
+        let cash_vault = get_associated_token_address(&setup.fund_account, &settlement_mint);
+        let buyer_payment_ata = get_associated_token_address(&setup.buyer.pubkey(), &settlement_mint);
+
+        initialize_fund_with_extra_accounts(
+            &setup, 
+            vec![
+                AccountMeta::new_readonly(settlement_mint, false),
+                AccountMeta::new(cash_vault, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ]
+        ).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                subscription_pricing: PricingMode::Forward, 
+                cancellation_cutoff_seconds: 60,
+                settlement_deadline_seconds: 3_600,
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        let payment_accounts = vec![
+            AccountMeta::new(buyer_payment_ata, false),
+            AccountMeta::new(cash_vault, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ];
+
+        request_subscription_request(&setup, 0, 20_000 * SCALE_FACTOR, payment_accounts.clone()).await
+            .unwrap();
+
+        advance_clock(&setup, 120).await;
+
+        let cutoff_error = cancel_subscription_request(&setup, 0, payment_accounts.clone()).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            cutoff_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::CancellationWindowClosed as u32)
+            )
+        );
+
+        // the manager never processed it, so the investor can take the escrow back
+        advance_clock(&setup, 3_600).await;
+
+        cancel_subscription_request(&setup, 0, payment_accounts).await.unwrap();
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let cash_vault_data = TokenAccount::unpack(
+            &banks_client.get_account(cash_vault).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let buyer_payment_data = TokenAccount::unpack(
+            &banks_client.get_account(buyer_payment_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let subscription_request_account = banks_client
+            .get_account(get_subscription_request_address(&setup, 0))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(cash_vault_data.amount, 0);
+        assert_eq!(buyer_payment_data.amount, 100_000 * SCALE_FACTOR);
+        assert_eq!(subscription_request_account.owner, SYSTEM_PROGRAM_ID);
+        assert!(subscription_request_account.data.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_forward_priced_redemption() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        init_nav_history(&setup).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                redemption_pricing: PricingMode::Forward, 
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        buy_shares(&setup).await;
+
+        redeem_shares(&setup).await;
+
+        let nav_history_address = get_nav_history_address(&setup);
+
+        advance_clock(&setup, 60).await;
+
+        update_share_value_with_extra_accounts(
+            &setup, 
+            11_000 * SCALE_FACTOR, 
+            vec![AccountMeta::new(nav_history_address, false)]
+        ).await;
+
+        advance_clock(&setup, 60).await;
+
+        update_share_value_with_extra_accounts(
+            &setup, 
+            12_000 * SCALE_FACTOR, 
+            vec![AccountMeta::new(nav_history_address, false)]
+        ).await;
+
+        process_share_redemption_request(
+            &setup, 
+            0, 
+            SCALE_FACTOR, 
+            11_000 * SCALE_FACTOR, 
+            vec![AccountMeta::new_readonly(nav_history_address, false)]
+        ).await.unwrap();
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let buyer_ata = get_associated_token_address(&setup.buyer.pubkey(), &setup.fund_mint);
+
+        let buyer_token_account_data = TokenAccount::unpack(
+            &banks_client.get_account(buyer_ata).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(buyer_token_account_data.amount, SCALE_FACTOR);
+    }
+
+    #[test]
+    fn test_nav_history_first_after() {
+        let mut nav_history = state::nav_history::NavHistory {
+            is_initialized: true,
+            bump_seed: 0,
+            fund_account: Pubkey::new_unique(),
+            head: 0,
+            entries: Vec::new(),
+        };
+
+        for timestamp in [100, 200, 300] {
+            nav_history.push(state::nav_history::NavEntry { 
+                timestamp, 
+                share_value: timestamp as u64 * SCALE_FACTOR 
+            });
+        }
+
+        assert_eq!(nav_history.first_after(150).unwrap().timestamp, 200);
+        assert_eq!(nav_history.first_after(200).unwrap().timestamp, 300);
+        assert_eq!(
+            nav_history.first_after(300).unwrap_err(), 
+            CustomError::AwaitingNextShareValue.into()
+        );
+
+        let capacity = state::nav_history::NavHistory::CAPACITY;
+
+        for timestamp in 0..capacity as i64 {
+            nav_history.push(state::nav_history::NavEntry { 
+                timestamp: 1_000 + timestamp, 
+                share_value: SCALE_FACTOR 
+            });
+        }
+
+        assert_eq!(
+            nav_history.first_after(500).unwrap_err(), 
+            CustomError::DealingShareValueUnavailable.into()
+        );
+        assert_eq!(nav_history.first_after(1_000).unwrap().timestamp, 1_001);
+    }
+
+    // Friday 2030-01-11 12:00 UTC, the first dealing cut-off after the
+    // 2030-01-04 holiday
+    const DEALING_CUT_OFF: i64 = 1_894_363_200;
+
+    fn weekly_friday_schedule(reject_outside_window: bool) -> DealingSchedule {
+        DealingSchedule {
+            frequency: DealingFrequency::Weekly { weekday: 4 },
+            cut_off_seconds: 12 * 3_600,
+            holidays: vec![20300104],
+            reject_outside_window
+        }
+    }
+
+    #[test]
+    fn test_dealing_schedule() {
+        let schedule = weekly_friday_schedule(false);
+
+        assert_eq!(
+            schedule.next_dealing_timestamp(DEALING_CUT_OFF - 10 * 86_400 - 2 * 3_600).unwrap(), 
+            Some(DEALING_CUT_OFF)
+        );
+        assert_eq!(
+            schedule.next_dealing_timestamp(DEALING_CUT_OFF).unwrap(), 
+            Some(DEALING_CUT_OFF + 7 * 86_400)
+        );
+
+        schedule.check_dealing_window(DEALING_CUT_OFF - 3_600).unwrap();
+        schedule.check_dealing_window(DEALING_CUT_OFF + 3_600).unwrap_err();
+
+        let month_end = DealingSchedule {
+            frequency: DealingFrequency::Monthly { day: 31 },
+            cut_off_seconds: 12 * 3_600,
+            ..DealingSchedule::default()
+        };
+
+        // 2030-02-03 00:00 UTC deals on 2030-02-28 12:00 UTC
+        assert_eq!(month_end.next_dealing_timestamp(1_896_307_200).unwrap(), Some(1_898_510_400));
+
+        assert_eq!(DealingSchedule::default().next_dealing_timestamp(0).unwrap(), None);
+
+        DealingSchedule { holidays: vec![20300230], ..weekly_friday_schedule(false) }
+            .validate()
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_dealing_calendar() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                dealing_schedule: weekly_friday_schedule(false), 
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        let outside_window_error = TransactionError::InstructionError(
+            0, 
+            InstructionError::Custom(CustomError::OutsideDealingWindow as u32)
+        );
+
+        set_clock(&setup, DEALING_CUT_OFF - 10 * 86_400).await;
+
+        let buy_error = buy_shares_request(&setup, 20_000 * SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(buy_error, outside_window_error);
+
+        set_clock(&setup, DEALING_CUT_OFF - 3_600).await;
+
+        buy_shares_request(&setup, 30_000 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        set_clock(&setup, DEALING_CUT_OFF + 3_600).await;
+
+        redeem_shares_request(&setup, 0, SCALE_FACTOR, vec![]).await.unwrap();
+
+        let share_redemption = try_from_slice_unchecked::<state::share_redemption::ShareRedemption>(
+            &setup.banks_client.lock().await
+                .get_account(get_share_redemption_address(&setup, 0))
+                .await
+                .unwrap()
+                .unwrap()
+                .data
+        ).unwrap();
+
+        assert_eq!(share_redemption.dealing_timestamp, DEALING_CUT_OFF + 7 * 86_400);
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                dealing_schedule: weekly_friday_schedule(true), 
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        let redeem_error = redeem_shares_request(&setup, 1, SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(redeem_error, outside_window_error);
+    }
+
+    async fn escalate_redemption_request(
+        setup: &TestSetup, 
+        request_id: u64
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let escalate_redemption_payload 
+            = EscalateRedemptionPayload { fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        escalate_redemption_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[15][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new_readonly(setup.fund_account, false),
+                AccountMeta::new(get_share_redemption_address(setup, request_id), false),
+                AccountMeta::new_readonly(setup.buyer.pubkey(), true),
+            ]
+        );
+
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                &setup.buyer
+            ], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_redemption_notice_and_settlement_deadline() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                redemption_notice_seconds: 86_400, 
+                settlement_deadline_seconds: 3 * 86_400,
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        buy_shares(&setup).await;
+
+        redeem_shares(&setup).await;
+
+        let custom_error = |error: CustomError| TransactionError::InstructionError(
+            0, 
+            InstructionError::Custom(error as u32)
+        );
+
+        let notice_error = process_share_redemption_request(
+            &setup, 
+            0, 
+            SCALE_FACTOR / 2, 
+            5_000 * SCALE_FACTOR, 
+            vec![]
+        ).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(notice_error, custom_error(CustomError::NoticePeriodNotElapsed));
+
+        let early_escalation_error = escalate_redemption_request(&setup, 0).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(early_escalation_error, custom_error(CustomError::SettlementDeadlineNotReached));
+
+        advance_clock(&setup, 4 * 86_400).await;
+
+        escalate_redemption_request(&setup, 0).await.unwrap();
+
+        let share_redemption = try_from_slice_unchecked::<state::share_redemption::ShareRedemption>(
+            &setup.banks_client.lock().await
+                .get_account(get_share_redemption_address(&setup, 0))
+                .await
+                .unwrap()
+                .unwrap()
+                .data
+        ).unwrap();
+
+        assert!(share_redemption.is_overdue);
+
+        let repeated_escalation_error = escalate_redemption_request(&setup, 0).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(repeated_escalation_error, custom_error(CustomError::RedemptionAlreadyEscalated));
+
+        process_share_redemption_request(&setup, 0, SCALE_FACTOR, 10_000 * SCALE_FACTOR, vec![]).await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_redemption_gate() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                redemption_gate_bps: 2_500, 
+                redemption_gate_period_seconds: 86_400,
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        buy_shares(&setup).await;
+
+        redeem_shares(&setup).await;
+
+        // the manager signs for the requested share, but only a quarter of the
+        // 2 share supply can leave in the first period
+        process_share_redemption_request(&setup, 0, SCALE_FACTOR, 10_000 * SCALE_FACTOR, vec![]).await
+            .unwrap();
+
+        let gate_error = process_share_redemption_request(
+            &setup, 
+            0, 
+            SCALE_FACTOR / 4, 
+            2_500 * SCALE_FACTOR, 
+            vec![]
+        ).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            gate_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::RedemptionGateClosed as u32)
+            )
+        );
+
+        advance_clock(&setup, 86_400).await;
+
+        // the next period is a quarter of the remaining 1.5 share supply
+        process_share_redemption_request(&setup, 0, SCALE_FACTOR / 2, 5_000 * SCALE_FACTOR, vec![]).await
+            .unwrap();
+
+        let share_redemption = try_from_slice_unchecked::<state::share_redemption::ShareRedemption>(
+            &setup.banks_client.lock().await
+                .get_account(get_share_redemption_address(&setup, 0))
+                .await
+                .unwrap()
+                .unwrap()
+                .data
+        ).unwrap();
+
+        assert_eq!(share_redemption.processed_shares, 875_000);
+        assert_eq!(share_redemption.shares_amount, 125_000);
+        assert_eq!(share_redemption.amount_payed, 8_750 * SCALE_FACTOR);
+    }
+
+    fn get_investor_lots_address(setup: &TestSetup) -> Pubkey {
+        let (investor_lots, _bump_seed) = Pubkey::find_program_address(
+            &[
+                b"investor_lots", 
+                setup.fund_name.as_bytes(), 
+                &setup.buyer.pubkey().to_bytes()
+            ], 
+            &setup.program_id
+        );
+
+        investor_lots
+    }
+
+    #[tokio::test]
+    async fn test_lock_up_period() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        let lock_up_config = FundConfig { 
+            lock_up_seconds: 365 * 86_400, 
+            ..FundConfig::default() 
+        };
+
+        update_fund_config(&setup, lock_up_config.clone()).await;
+
+        let investor_lots = vec![AccountMeta::new(get_investor_lots_address(&setup), false)];
+
+        buy_shares_request(&setup, 20_000 * SCALE_FACTOR, investor_lots.clone()).await.unwrap();
+
+        let locked_error = redeem_shares_request(&setup, 0, 2 * SCALE_FACTOR, investor_lots.clone()).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            locked_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::SharesLockedUp as u32)
+            )
+        );
+
+        // an unfunded account in place of the lots PDA must not skip the lock-up
+        let bogus_lots = vec![AccountMeta::new(Pubkey::new_unique(), false)];
+
+        let bogus_lots_error = redeem_shares_request(&setup, 0, 2 * SCALE_FACTOR, bogus_lots).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            bogus_lots_error, 
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+        );
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                early_redemption_fee_bps: 500, 
+                ..lock_up_config 
+            }
+        ).await;
+
+        redeem_shares_request(&setup, 0, SCALE_FACTOR, investor_lots.clone()).await.unwrap();
+
+        advance_clock(&setup, 366 * 86_400).await;
+
+        redeem_shares_request(&setup, 1, SCALE_FACTOR / 2, investor_lots).await.unwrap();
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let early_redemption = try_from_slice_unchecked::<state::share_redemption::ShareRedemption>(
+            &banks_client.get_account(get_share_redemption_address(&setup, 0)).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let late_redemption = try_from_slice_unchecked::<state::share_redemption::ShareRedemption>(
+            &banks_client.get_account(get_share_redemption_address(&setup, 1)).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let mint_data = Mint::unpack(
+            &banks_client.get_account(setup.fund_mint).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(early_redemption.shares_amount, SCALE_FACTOR * 95 / 100);
+        assert_eq!(late_redemption.shares_amount, SCALE_FACTOR / 2);
+        assert_eq!(mint_data.supply, 2 * SCALE_FACTOR - SCALE_FACTOR * 5 / 100);
+    }
+
+    #[tokio::test]
+    async fn test_investment_limits() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                min_initial_investment: 10_000 * SCALE_FACTOR, 
+                min_top_up: 5_000 * SCALE_FACTOR,
+                min_redemption_shares: SCALE_FACTOR / 2,
+                max_holding_shares: 3 * SCALE_FACTOR,
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        let custom_error = |error: CustomError| TransactionError::InstructionError(
+            0, 
+            InstructionError::Custom(error as u32)
+        );
+
+        let initial_error = buy_shares_request(&setup, 5_000 * SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(initial_error, custom_error(CustomError::BelowMinimumInitialInvestment));
+
+        buy_shares_request(&setup, 20_000 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        let top_up_error = buy_shares_request(&setup, 1_000 * SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(top_up_error, custom_error(CustomError::BelowMinimumTopUp));
+
+        let holding_error = buy_shares_request(&setup, 15_000 * SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(holding_error, custom_error(CustomError::AboveMaximumHolding));
+
+        let redemption_error = redeem_shares_request(&setup, 0, SCALE_FACTOR / 4, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(redemption_error, custom_error(CustomError::BelowMinimumRedemption));
+
+        update_fund_config(&setup, FundConfig::default()).await;
+
+        let zero_shares_error = buy_shares_request(&setup, 1, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(zero_shares_error, custom_error(CustomError::ZeroSharesIssued));
+    }
+
+    fn get_investor_record_address(setup: &TestSetup, investor: &Pubkey) -> Pubkey {
+        let (investor_record, _bump_seed) = Pubkey::find_program_address(
+            &[
+                b"investor_record", 
+                &setup.fund_account.to_bytes(), 
+                &investor.to_bytes()
+            ], 
+            &setup.program_id
+        );
+
+        investor_record
+    }
+
+    async fn add_investor_request(
+        setup: &TestSetup, 
+        compliance_officer: &Keypair,
+        investor: &Pubkey,
+        expiry_timestamp: i64
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let add_investor_payload = AddInvestorPayload { 
+            expiry_timestamp, 
+            jurisdiction: *b"ES", 
+            fund_name: setup.fund_name.clone() 
+        };
+
+        let mut payload_data = Vec::new();
+        add_investor_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[16][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+                AccountMeta::new_readonly(compliance_officer.pubkey(), true),
+                AccountMeta::new_readonly(setup.fund_account, false),
+                AccountMeta::new(get_investor_record_address(setup, investor), false),
+                AccountMeta::new_readonly(*investor, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            ]
+        );
+
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                compliance_officer
+            ], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    async fn set_investor_status_request(
+        setup: &TestSetup, 
+        compliance_officer: &Keypair,
+        instruction_discriminator: u8
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let investor_status_payload 
+            = InvestorStatusPayload { fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        investor_status_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[instruction_discriminator][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new_readonly(compliance_officer.pubkey(), true),
+                AccountMeta::new_readonly(setup.fund_account, false),
+                AccountMeta::new(get_investor_record_address(setup, &setup.buyer.pubkey()), false),
+            ]
+        );
+
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                compliance_officer
+            ], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_investor_kyc_registry() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        let compliance_officer = Keypair::new();
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                compliance_officer: compliance_officer.pubkey(), 
+                kyc_required: true,
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        let custom_error = |error: CustomError| TransactionError::InstructionError(
+            0, 
+            InstructionError::Custom(error as u32)
+        );
+
+        let investor_record = vec![AccountMeta::new_readonly(get_investor_record_address(&setup, &setup.buyer.pubkey()), false)];
+
+        let unknown_error = buy_shares_request(&setup, 20_000 * SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(unknown_error, custom_error(CustomError::InvestorNotApproved));
+
+        let impostor_error = add_investor_request(&setup, &Keypair::new(), &setup.buyer.pubkey(), i64::MAX).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            impostor_error, 
+            TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature)
+        );
+
+        let current_timestamp = setup.context.lock().await
+            .banks_client.get_sysvar::<Clock>().await.unwrap()
+            .unix_timestamp;
+
+        add_investor_request(&setup, &compliance_officer, &setup.buyer.pubkey(), current_timestamp + 86_400).await.unwrap();
+
+        buy_shares_request(&setup, 20_000 * SCALE_FACTOR, investor_record.clone()).await.unwrap();
+
+        advance_clock(&setup, 2 * 86_400).await;
+
+        let expired_error = redeem_shares_request(&setup, 0, SCALE_FACTOR, investor_record.clone()).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(expired_error, custom_error(CustomError::KycExpired));
+
+        add_investor_request(&setup, &compliance_officer, &setup.buyer.pubkey(), i64::MAX).await.unwrap();
+
+        redeem_shares_request(&setup, 0, SCALE_FACTOR / 2, investor_record.clone()).await.unwrap();
+
+        set_investor_status_request(&setup, &compliance_officer, 17).await.unwrap();
+
+        let suspended_error = redeem_shares_request(&setup, 1, SCALE_FACTOR / 4, investor_record.clone()).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(suspended_error, custom_error(CustomError::InvestorNotApproved));
+
+        set_investor_status_request(&setup, &compliance_officer, 18).await.unwrap();
+
+        let revoked_error = add_investor_request(&setup, &compliance_officer, &setup.buyer.pubkey(), i64::MAX - 1).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(revoked_error, custom_error(CustomError::InvestorRevoked));
+    }
+
+    #[tokio::test]
+    async fn test_subscription_request_rechecks_kyc() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        let compliance_officer = Keypair::new();
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                compliance_officer: compliance_officer.pubkey(), 
+                kyc_required: true,
+                subscription_pricing: PricingMode::Forward,
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        init_nav_history(&setup).await;
+
+        let nav_history = vec![AccountMeta::new(get_nav_history_address(&setup), false)];
+
+        let investor_record = vec![AccountMeta::new_readonly(get_investor_record_address(&setup, &setup.buyer.pubkey()), false)];
+
+        add_investor_request(&setup, &compliance_officer, &setup.buyer.pubkey(), i64::MAX).await.unwrap();
+
+        request_subscription_request(&setup, 0, 20_000 * SCALE_FACTOR, investor_record.clone()).await.unwrap();
+
+        set_investor_status_request(&setup, &compliance_officer, 17).await.unwrap();
+
+        advance_clock(&setup, 60).await;
+
+        update_share_value_with_extra_accounts(&setup, 12_500 * SCALE_FACTOR, nav_history.clone()).await;
+
+        let suspended_error = process_subscription_request(&setup, 0, [nav_history.clone(), investor_record].concat()).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            suspended_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::InvestorNotApproved as u32)
+            )
+        );
+
+        let missing_record_error = process_subscription_request(&setup, 0, nav_history).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            missing_record_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::InvestorNotApproved as u32)
+            )
+        );
+    }
+
+    async fn get_token_2022_balance(setup: &TestSetup, owner: &Pubkey) -> u64 {
+        let token_account = setup.banks_client.lock().await
+            .get_account(get_associated_token_address_with_program_id(
+                owner, 
+                &setup.fund_mint, 
+                &TOKEN_2022_PROGRAM_ID
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+
+        StateWithExtensions::<Token2022Account>::unpack(&token_account.data).unwrap().base.amount
+    }
+
+    async fn transfer_shares_request(
+        setup: &TestSetup, 
+        recipient: &Pubkey,
+        amount: u64
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let mut instruction = transfer_checked(
+            &TOKEN_2022_PROGRAM_ID, 
+            &get_associated_token_address_with_program_id(
+                &setup.buyer.pubkey(), 
+                &setup.fund_mint, 
+                &TOKEN_2022_PROGRAM_ID
+            ), 
+            &setup.fund_mint, 
+            &get_associated_token_address_with_program_id(
+                recipient, 
+                &setup.fund_mint, 
+                &TOKEN_2022_PROGRAM_ID
+            ), 
+            &setup.buyer.pubkey(), 
+            &[], 
+            amount, 
+            6
+        ).unwrap();
+
+        instruction.accounts.extend([
+            AccountMeta::new_readonly(get_extra_account_metas_address(&setup.fund_mint, &setup.program_id), false),
+            AccountMeta::new_readonly(setup.fund_account, false),
+            AccountMeta::new_readonly(get_investor_record_address(setup, &setup.buyer.pubkey()), false),
+            AccountMeta::new_readonly(get_investor_record_address(setup, recipient), false),
+            AccountMeta::new_readonly(setup.program_id, false),
+        ]);
+
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                &setup.buyer
+            ], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_transfer_restricted_share_mint() {
+        let setup = get_setup_with_token_program(TOKEN_2022_PROGRAM_ID).await;
+
+        initialize_fund_with_extra_accounts(
+            &setup, 
+            vec![AccountMeta::new(get_extra_account_metas_address(&setup.fund_mint, &setup.program_id), false)]
+        ).await;
+
+        let mint_account = setup.banks_client.lock().await
+            .get_account(setup.fund_mint).await.unwrap().unwrap();
+
+        let mint_data = StateWithExtensions::<Token2022Mint>::unpack(&mint_account.data).unwrap();
+        let transfer_hook = mint_data.get_extension::<TransferHook>().unwrap();
+
+        assert_eq!(mint_account.owner, TOKEN_2022_PROGRAM_ID);
+        assert_eq!(Option::<Pubkey>::from(transfer_hook.program_id), Some(setup.program_id));
+
+        let compliance_officer = Keypair::new();
+
+        let restricted_config = FundConfig { 
+            compliance_officer: compliance_officer.pubkey(), 
+            ..FundConfig::default() 
+        };
+
+        update_fund_config(&setup, restricted_config.clone()).await;
+
+        buy_shares_request(&setup, 20_000 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        let recipient = Keypair::new().pubkey();
+
+        {
+            let mut context = setup.context.lock().await;
+
+            let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+            let transaction = Transaction::new_signed_with_payer(
+                &[create_associated_token_account(
+                    &setup.punto_xero_master.pubkey(), 
+                    &recipient, 
+                    &setup.fund_mint, 
+                    &TOKEN_2022_PROGRAM_ID
+                )], 
+                Some(&setup.punto_xero_master.pubkey()), 
+                &[&setup.punto_xero_master], 
+                recent_blockhash
+            );
+
+            context.banks_client.process_transaction(transaction).await.unwrap();
+        }
+
+        let custom_error = |error: CustomError| TransactionError::InstructionError(
+            0, 
+            InstructionError::Custom(error as u32)
+        );
+
+        let unlisted_error = transfer_shares_request(&setup, &recipient, SCALE_FACTOR / 2).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(unlisted_error, custom_error(CustomError::InvestorNotApproved));
+
+        add_investor_request(&setup, &compliance_officer, &setup.buyer.pubkey(), i64::MAX).await.unwrap();
+        add_investor_request(&setup, &compliance_officer, &recipient, i64::MAX).await.unwrap();
+
+        transfer_shares_request(&setup, &recipient, SCALE_FACTOR / 2).await.unwrap();
+
+        assert_eq!(get_token_2022_balance(&setup, &recipient).await, SCALE_FACTOR / 2);
+
+        // the program moves hooked shares by burning and minting
+        redeem_shares_request(&setup, 0, SCALE_FACTOR / 4, vec![]).await.unwrap();
+
+        assert_eq!(get_token_2022_balance(&setup, &setup.fund_account).await, SCALE_FACTOR / 4);
+
+        cancel_redemption(&setup, 0).await.unwrap();
+
+        assert_eq!(get_token_2022_balance(&setup, &setup.fund_account).await, 0);
+        assert_eq!(get_token_2022_balance(&setup, &setup.buyer.pubkey()).await, 3 * SCALE_FACTOR / 2);
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                share_transfer_policy: ShareTransferPolicy::Blocked, 
+                ..restricted_config 
+            }
+        ).await;
+
+        let blocked_error = transfer_shares_request(&setup, &recipient, SCALE_FACTOR / 4).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(blocked_error, custom_error(CustomError::ShareTransfersBlocked));
+    }
+
+    async fn set_investor_frozen_request(
+        setup: &TestSetup, 
+        compliance_officer: &Keypair,
+        instruction_discriminator: u8
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let investor_status_payload 
+            = InvestorStatusPayload { fund_name: setup.fund_name.clone() };
+
+        let mut payload_data = Vec::new();
+        investor_status_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[instruction_discriminator][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new_readonly(compliance_officer.pubkey(), true),
+                AccountMeta::new_readonly(setup.fund_account, false),
+                AccountMeta::new_readonly(setup.fund_mint, false),
+                AccountMeta::new_readonly(setup.buyer.pubkey(), false),
+                AccountMeta::new(get_associated_token_address(&setup.buyer.pubkey(), &setup.fund_mint), false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ]
+        );
+
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                compliance_officer
+            ], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    #[tokio::test]
+    async fn test_freeze_investor() {
+        let setup = get_setup().await;
+
+        initialize_fund_and_mint(&setup).await;
+
+        let mint_data = Mint::unpack(
+            &setup.banks_client.lock().await.get_account(setup.fund_mint).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(Option::<Pubkey>::from(mint_data.freeze_authority), Some(setup.fund_account));
+
+        let compliance_officer = Keypair::new();
+
+        update_fund_config(
+            &setup, 
+            FundConfig { 
+                compliance_officer: compliance_officer.pubkey(), 
+                ..FundConfig::default() 
+            }
+        ).await;
+
+        buy_shares(&setup).await;
+
+        let impostor_error = set_investor_frozen_request(&setup, &Keypair::new(), 19).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            impostor_error, 
+            TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature)
+        );
+
+        set_investor_frozen_request(&setup, &compliance_officer, 19).await.unwrap();
+
+        let buyer_ata = setup.banks_client.lock().await
+            .get_account(get_associated_token_address(&setup.buyer.pubkey(), &setup.fund_mint))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(TokenAccount::unpack(&buyer_ata.data).unwrap().is_frozen());
+
+        let frozen_error = redeem_shares_request(&setup, 0, SCALE_FACTOR, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            frozen_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(spl_token::error::TokenError::AccountFrozen as u32)
+            )
+        );
+
+        set_investor_frozen_request(&setup, &compliance_officer, 20).await.unwrap();
+
+        redeem_shares_request(&setup, 0, SCALE_FACTOR / 2, vec![]).await.unwrap();
+    }
+
+    async fn update_share_metadata_request(
+        setup: &TestSetup, 
+        symbol: &str,
+        uri: &str
+    ) -> Result<(), BanksClientError> {
+        let mut context = setup.context.lock().await;
+
+        let update_share_metadata_payload = UpdateShareMetadataPayload { 
+            symbol: symbol.to_owned(), 
+            uri: uri.to_owned(), 
+            fund_name: setup.fund_name.clone() 
+        };
+
+        let mut payload_data = Vec::new();
+        update_share_metadata_payload.serialize(&mut payload_data)
+            .unwrap();
+
+        let instruction = Instruction::new_with_bytes(
+            setup.program_id, 
+            &[&[21][..], &payload_data].concat(),
+            vec![
+                AccountMeta::new(setup.punto_xero_master.pubkey(), true),
+                AccountMeta::new_readonly(setup.fund_manager_master.pubkey(), true),
+                AccountMeta::new_readonly(setup.fund_account, false),
+                AccountMeta::new(setup.fund_mint, false),
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+                AccountMeta::new_readonly(setup.token_program, false),
+            ]
+        );
+
+        let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction], 
+            Some(&setup.punto_xero_master.pubkey()), 
+            &[
+                &setup.punto_xero_master,
+                &setup.fund_manager_master
+            ], 
+            recent_blockhash
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    async fn get_share_metadata(setup: &TestSetup) -> TokenMetadata {
+        let mint_account = setup.banks_client.lock().await
+            .get_account(setup.fund_mint).await.unwrap().unwrap();
+
+        StateWithExtensions::<Token2022Mint>::unpack(&mint_account.data).unwrap()
+            .get_variable_len_extension::<TokenMetadata>()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_share_metadata() {
+        let setup = get_setup_with_token_program(TOKEN_2022_PROGRAM_ID).await;
+
+        initialize_fund_with_extra_accounts(
+            &setup, 
+            vec![AccountMeta::new(get_extra_account_metas_address(&setup.fund_mint, &setup.program_id), false)]
+        ).await;
+
+        let share_metadata = get_share_metadata(&setup).await;
+
+        assert_eq!(share_metadata.name, setup.fund_name);
+        assert_eq!(share_metadata.symbol, "PXF");
+        assert_eq!(share_metadata.uri, "https://puntoxero.com/fund.json");
+        assert_eq!(Option::<Pubkey>::from(share_metadata.update_authority), Some(setup.fund_account));
+
+        update_share_metadata_request(&setup, "PXGF", "https://puntoxero.com/funds/global-equity.json").await
+            .unwrap();
+
+        let share_metadata = get_share_metadata(&setup).await;
+
+        assert_eq!(share_metadata.symbol, "PXGF");
+        assert_eq!(share_metadata.uri, "https://puntoxero.com/funds/global-equity.json");
+
+        let classic_setup = get_setup().await;
+
+        let classic_metadata_error = initialize_fund_request(
+            &classic_setup, 
+            6, 
+            String::from("PXF"), 
+            String::new(), 
+            vec![]
+        ).await.unwrap_err().unwrap();
+
+        assert_eq!(
+            classic_metadata_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::MintHasNoMetadata as u32)
+            )
+        );
+
+        initialize_fund_and_mint(&classic_setup).await;
+
+        let no_metadata_error = update_share_metadata_request(&classic_setup, "PXF", "").await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            no_metadata_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::MintHasNoMetadata as u32)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_share_decimals() {
+        let setup = get_setup().await;
+
+        initialize_fund_with_share_decimals(&setup, 0, vec![]).await;
+
+        let mut banks_client = setup.banks_client.lock().await;
+
+        let mint_data = Mint::unpack(
+            &banks_client.get_account(setup.fund_mint).await.unwrap().unwrap().data
+        ).unwrap();
+
+        let fund_account_data = try_from_slice_unchecked::<state::fund_account::FundAccount>(
+            &banks_client.get_account(setup.fund_account).await.unwrap().unwrap().data
+        ).unwrap();
+
+        assert_eq!(mint_data.decimals, 0);
+        assert_eq!(fund_account_data.share_decimals, 0);
+
+        drop(banks_client);
+
+        // whole shares at 10,000 each
+        buy_shares_request(&setup, 25_000 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        let buyer_ata = setup.banks_client.lock().await
+            .get_account(get_associated_token_address(&setup.buyer.pubkey(), &setup.fund_mint))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(TokenAccount::unpack(&buyer_ata.data).unwrap().amount, 2);
+
+        redeem_shares_request(&setup, 0, 1, vec![]).await.unwrap();
+
+        let wrong_amount_error = process_share_redemption_request(&setup, 0, 1, 10_000, vec![]).await
+            .unwrap_err()
+            .unwrap();
+
+        assert_eq!(
+            wrong_amount_error, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(CustomError::InvalidRedemptionAmount as u32)
+            )
+        );
+
+        process_share_redemption_request(&setup, 0, 1, 10_000 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        // nine decimals must not overflow at ordinary fund sizes
+        let precise_setup = get_setup().await;
+
+        initialize_fund_with_share_decimals(&precise_setup, 9, vec![]).await;
+
+        buy_shares_request(&precise_setup, 20_000 * SCALE_FACTOR, vec![]).await.unwrap();
+
+        let buyer_ata = precise_setup.banks_client.lock().await
+            .get_account(get_associated_token_address(&precise_setup.buyer.pubkey(), &precise_setup.fund_mint))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(TokenAccount::unpack(&buyer_ata.data).unwrap().amount, 2_000_000_000);
+
+        redeem_shares_request(&precise_setup, 0, 2_000_000_000, vec![]).await.unwrap();
+
+        process_share_redemption_request(&precise_setup, 0, 2_000_000_000, 20_000 * SCALE_FACTOR, vec![]).await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_fixed_point_share_scale() {
+        assert_eq!(utils::fixed_point_divide_checked(25_000, 10_000, 0).unwrap(), 2);
+        assert_eq!(utils::fixed_point_divide_checked(25_000, 10_000, 9).unwrap(), 2_500_000_000);
+        assert_eq!(utils::fixed_point_multiply_checked(2_500_000_000, 10_000, 9).unwrap(), 25_000);
+        assert_eq!(
+            utils::fixed_point_multiply_checked(10_000_000_000, 2_000_000_000, 9).unwrap(), 
+            20_000_000_000
+        );
+        assert!(utils::fixed_point_multiply_checked(u64::MAX, u64::MAX, 0).is_err());
+        assert!(utils::share_scale(20).is_err());
+    }
+}
@@ -2,30 +2,81 @@
 use solana_program::program_error::ProgramError;
 use crate::errors::CustomError;
 
-pub fn fixed_point_divide_checked(a: u64, b: u64) -> Result<u64, ProgramError> {
+// fixed point amounts carry as many decimals as the fund's shares
+pub fn share_scale(share_decimals: u8) -> Result<u64, ProgramError> {
+    10u64.checked_pow(share_decimals as u32)
+        .ok_or::<ProgramError>(CustomError::ArithmeticError.into())
+}
+
+pub fn fixed_point_divide_checked(a: u64, b: u64, share_decimals: u8) -> Result<u64, ProgramError> {
     if b == 0 {
         return Err(CustomError::ArithmeticError.into());
     }
 
-    const SCALE: u64 = 1_000_000;
+    let scaled_a = (a as u128)
+        .checked_mul(share_scale(share_decimals)? as u128)
+        .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
 
-    let scaled_a = a.checked_mul(SCALE)
+    let result = scaled_a / b as u128;
+
+    u64::try_from(result)
+        .map_err(|_| CustomError::ArithmeticError.into())
+}
+
+pub fn fixed_point_multiply_checked(a: u64, b: u64, share_decimals: u8) -> Result<u64, ProgramError> {
+    let product = (a as u128)
+        .checked_mul(b as u128)
         .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
 
-    let result = scaled_a.checked_div(b)
-    .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
+    let result = product / share_scale(share_decimals)? as u128;
 
-    Ok(result)
+    u64::try_from(result)
+        .map_err(|_| CustomError::ArithmeticError.into())
 }
 
-pub fn fixed_point_multiply_checked(a: u64, b: u64) -> Result<u64, ProgramError> {
-    const SCALE: u64 = 1_000_000;
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+pub const BASIS_POINTS: u64 = 10_000;
+
+pub fn basis_points_of(amount: u64, bps: u16) -> Result<u64, ProgramError> {
+    let result = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?
+        / BASIS_POINTS as u128;
+
+    u64::try_from(result)
+        .map_err(|_| CustomError::ArithmeticError.into())
+}
 
-    let product = a.checked_mul(b)
+pub fn management_fee_shares(supply: u64, fee_bps: u16, elapsed_seconds: u64) -> Result<u64, ProgramError> {
+    let numerator = (supply as u128)
+        .checked_mul(fee_bps as u128)
+        .and_then(|value| value.checked_mul(elapsed_seconds as u128))
         .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
-    
-    let result = product.checked_div(SCALE)
+
+    let result = numerator / (BASIS_POINTS as u128 * SECONDS_PER_YEAR as u128);
+
+    u64::try_from(result)
+        .map_err(|_| CustomError::ArithmeticError.into())
+}
+
+pub fn performance_fee_shares(
+    supply: u64, 
+    share_value: u64, 
+    high_water_mark: u64, 
+    fee_bps: u16
+) -> Result<u64, ProgramError> {
+    if share_value <= high_water_mark {
+        return Ok(0);
+    }
+
+    let numerator = (supply as u128)
+        .checked_mul((share_value - high_water_mark) as u128)
+        .and_then(|value| value.checked_mul(fee_bps as u128))
         .ok_or::<ProgramError>(CustomError::ArithmeticError.into())?;
 
-    Ok(result)
-}
\ No newline at end of file
+    let result = numerator / (BASIS_POINTS as u128 * share_value as u128);
+
+    u64::try_from(result)
+        .map_err(|_| CustomError::ArithmeticError.into())
+}